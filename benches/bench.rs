@@ -1,4 +1,5 @@
 #![allow(unknown_lints)]
+#![allow(unexpected_cfgs)]
 
 #[macro_use]
 extern crate bencher;
@@ -14,17 +15,20 @@ use bencher::{black_box, Bencher};
 use rand::{SeedableRng, Rng, RngCore};
 use rand::{XorShiftRng, IsaacRng, Isaac64Rng, OsRng, ChaChaRng};
 use xoroshiro::rng::{XoroShiro128, SplitMix64, XorShift1024};
+use xoroshiro::rng::{Product, Scrambled, XoroShiro128Engine, XoroShiro128Params2016};
+
+/// The early xoroshiro128* variant, multiplying `s0` and `s1` with the
+/// original constants (55, 14, 36).
+type XoroShiro128Star = Scrambled<XoroShiro128Engine<XoroShiro128Params2016>, Product>;
 
 //#[cfg(feature = "unstable")]
 //mod aes;
-//mod xoroshiro128star;
 //mod smallprng;
 //mod sfc64;
 //mod urng;
 
 //#[cfg(feature = "unstable")]
 //use aes::AesRng;
-//use xoroshiro128star::XoroShiro128 as XoroShiro128Star;
 //use smallprng::SmallPrng128;
 //use sfc64::Sfc64;
 //use urng::Urng64;
@@ -81,7 +85,7 @@ make_bench_u64!(rand_u64_xorshift1024, XorShift1024);
 make_bench_u64!(rand_u64_splitmix, SplitMix64);
 //#[cfg(feature = "unstable")]
 //make_bench_u64!(rand_u64_aes, AesRng);
-//make_bench_u64!(rand_u64_xoroshirostar, XoroShiro128Star);
+make_bench_u64!(rand_u64_xoroshirostar, XoroShiro128Star);
 //make_bench_u64!(rand_u64_smallprng, SmallPrng128);
 //make_bench_u64!(rand_u64_sfc64, Sfc64);
 //make_bench_u64!(rand_u64_urng64, Urng64);
@@ -97,7 +101,7 @@ make_bench_f64!(rand_f64_xorshift1024, XorShift1024);
 make_bench_f64!(rand_f64_splitmix, SplitMix64);
 //#[cfg(feature = "unstable")]
 //make_bench_f64!(rand_f64_aes, AesRng);
-//make_bench_f64!(rand_f64_xoroshirostar, XoroShiro128Star);
+make_bench_f64!(rand_f64_xoroshirostar, XoroShiro128Star);
 //make_bench_f64!(rand_f64_smallprng, SmallPrng128);
 //make_bench_f64!(rand_f64_sfc64, Sfc64);
 //make_bench_f64!(rand_f64_urng64, Urng64);
@@ -113,7 +117,7 @@ make_bench_bytes!(rand_bytes_xorshift1024, XorShift1024);
 make_bench_bytes!(rand_bytes_splitmix, SplitMix64);
 //#[cfg(feature = "unstable")]
 //make_bench_bytes!(rand_bytes_aes, AesRng);
-//make_bench_bytes!(rand_bytes_xoroshirostar, XoroShiro128Star);
+make_bench_bytes!(rand_bytes_xoroshirostar, XoroShiro128Star);
 //make_bench_bytes!(rand_bytes_smallprng, SmallPrng128);
 //make_bench_bytes!(rand_bytes_sfc64, Sfc64);
 //make_bench_bytes!(rand_bytes_urng64, Urng64);
@@ -129,7 +133,7 @@ benchmark_group!(benches,
     rand_u64_xorshift1024,
     rand_u64_splitmix,
     //rand_u64_aes,
-    //rand_u64_xoroshirostar,
+    rand_u64_xoroshirostar,
     //rand_u64_smallprng,
     //rand_u64_sfc64,
     //rand_u64_urng64,
@@ -143,7 +147,7 @@ benchmark_group!(benches,
     rand_f64_xorshift1024,
     rand_f64_splitmix,
     //rand_f64_aes,
-    //rand_f64_xoroshirostar,
+    rand_f64_xoroshirostar,
     //rand_f64_smallprng,
     //rand_f64_sfc64,
     //rand_f64_urng64,
@@ -157,7 +161,7 @@ benchmark_group!(benches,
     rand_bytes_xorshift1024,
     rand_bytes_splitmix,
     //rand_bytes_aes,
-    //rand_bytes_xoroshirostar,
+    rand_bytes_xoroshirostar,
     //rand_bytes_smallprng,
     //rand_bytes_sfc64,
     //rand_bytes_urng64
@@ -171,7 +175,7 @@ benchmark_group!(benches,
     rand_u64_xoroshiro128,
     rand_u64_xorshift1024,
     rand_u64_splitmix,
-    //rand_u64_xoroshirostar,
+    rand_u64_xoroshirostar,
     //rand_u64_smallprng,
     //rand_u64_sfc64,
     //rand_u64_urng64,
@@ -183,7 +187,7 @@ benchmark_group!(benches,
     rand_f64_xoroshiro128,
     rand_f64_xorshift1024,
     rand_f64_splitmix,
-    //rand_f64_xoroshirostar,
+    rand_f64_xoroshirostar,
     //rand_f64_smallprng,
     //rand_f64_sfc64,
     //rand_f64_urng64,
@@ -195,7 +199,7 @@ benchmark_group!(benches,
     rand_bytes_xoroshiro128,
     rand_bytes_xorshift1024,
     rand_bytes_splitmix,
-    //rand_bytes_xoroshirostar,
+    rand_bytes_xoroshirostar,
     //rand_bytes_smallprng,
     //rand_bytes_sfc64,
     //rand_bytes_urng64
@@ -0,0 +1,188 @@
+use std::cmp;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+
+use byteorder::{LittleEndian, ByteOrder};
+
+use super::mix64;
+
+/// A fast, seedable `Hasher` built on `mix64`.
+///
+/// All writes are equivalent to writing the little-endian bytes of the
+/// written values, so the hash only depends on the concatenated byte stream
+/// and the seed, not on how it is split into writes.  `usize` is written as
+/// 64 bits on all platforms.  Each complete 8-byte word is absorbed by
+/// `state = mix64(state ^ word)`, writing a `u64` at a word boundary avoids
+/// the byte buffer.
+///
+/// For a given seed and byte stream, the hash is stable across versions and
+/// platforms.  Note that the byte streams written by the `Hash`
+/// implementations of the standard library may change between Rust versions.
+///
+/// This is not a cryptographic hash.  A random seed, as used by
+/// `SplitMixBuildHasher::new`, makes collisions hard to predict, but is not
+/// meant to resist attacks.
+#[derive(Debug, Clone)]
+pub struct SplitMixHasher {
+    state: u64,
+    // Pending bytes, in little-endian order.
+    buf: u64,
+    buf_len: usize,
+    len: u64,
+}
+
+impl SplitMixHasher {
+    /// Create a hasher with the given seed.
+    pub fn with_seed(seed: u64) -> SplitMixHasher {
+        SplitMixHasher {
+            state: seed,
+            buf: 0,
+            buf_len: 0,
+            len: 0,
+        }
+    }
+
+    #[inline]
+    fn absorb(&mut self, word: u64) {
+        self.state = mix64(self.state ^ word);
+    }
+}
+
+impl Default for SplitMixHasher {
+    /// Create a hasher with the seed 0.
+    fn default() -> SplitMixHasher {
+        SplitMixHasher::with_seed(0)
+    }
+}
+
+impl Hasher for SplitMixHasher {
+    fn write(&mut self, mut bytes: &[u8]) {
+        self.len = self.len.wrapping_add(bytes.len() as u64);
+        if self.buf_len > 0 {
+            let n = cmp::min(8 - self.buf_len, bytes.len());
+            for (i, &b) in bytes[..n].iter().enumerate() {
+                self.buf |= u64::from(b) << (8 * (self.buf_len + i));
+            }
+            self.buf_len += n;
+            bytes = &bytes[n..];
+            if self.buf_len < 8 {
+                return;
+            }
+            let word = self.buf;
+            self.absorb(word);
+            self.buf = 0;
+            self.buf_len = 0;
+        }
+        let mut chunks = bytes.chunks_exact(8);
+        for chunk in &mut chunks {
+            self.absorb(LittleEndian::read_u64(chunk));
+        }
+        for (i, &b) in chunks.remainder().iter().enumerate() {
+            self.buf |= u64::from(b) << (8 * i);
+        }
+        self.buf_len = chunks.remainder().len();
+    }
+
+    #[inline]
+    fn write_u8(&mut self, i: u8) {
+        self.write(&[i]);
+    }
+
+    #[inline]
+    fn write_u16(&mut self, i: u16) {
+        self.write(&i.to_le_bytes());
+    }
+
+    #[inline]
+    fn write_u32(&mut self, i: u32) {
+        self.write(&i.to_le_bytes());
+    }
+
+    #[inline]
+    fn write_u64(&mut self, i: u64) {
+        if self.buf_len == 0 {
+            self.len = self.len.wrapping_add(8);
+            self.absorb(i);
+        } else {
+            self.write(&i.to_le_bytes());
+        }
+    }
+
+    #[inline]
+    fn write_u128(&mut self, i: u128) {
+        self.write_u64(i as u64);
+        self.write_u64((i >> 64) as u64);
+    }
+
+    #[inline]
+    fn write_usize(&mut self, i: usize) {
+        self.write_u64(i as u64);
+    }
+
+    /// Return the hash of the bytes written so far.
+    ///
+    /// Pending bytes are absorbed as a zero-padded word, and the total length
+    /// is mixed in, so that streams differing only by trailing zeros have
+    /// different hashes.
+    fn finish(&self) -> u64 {
+        let mut state = self.state;
+        if self.buf_len > 0 {
+            state = mix64(state ^ self.buf);
+        }
+        mix64(state.wrapping_add(self.len))
+    }
+}
+
+/// A `BuildHasher` creating `SplitMixHasher`s with the same seed.
+///
+/// `new` and `default` choose a random seed for each instance, like
+/// `RandomState` of the standard library.  `with_seed` gives a fixed seed, for
+/// instance to make the iteration order of a `HashMap` reproducible in tests.
+///
+/// ```
+/// use std::collections::HashMap;
+/// use xoroshiro::hash::SplitMixBuildHasher;
+///
+/// let mut map = HashMap::with_hasher(SplitMixBuildHasher::with_seed(42));
+/// map.insert(1, "one");
+/// assert_eq!(map[&1], "one");
+/// ```
+#[derive(Debug, Clone)]
+pub struct SplitMixBuildHasher {
+    seed: u64,
+}
+
+impl SplitMixBuildHasher {
+    /// Create a builder with a random seed.
+    ///
+    /// The seed is derived from the random keys of the standard library's
+    /// `RandomState`, so it differs between instances and processes.
+    pub fn new() -> SplitMixBuildHasher {
+        SplitMixBuildHasher::with_seed(RandomState::new().build_hasher().finish())
+    }
+
+    /// Create a builder with a fixed seed.
+    pub fn with_seed(seed: u64) -> SplitMixBuildHasher {
+        SplitMixBuildHasher { seed }
+    }
+
+    /// Return the seed of the hashers.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+}
+
+impl Default for SplitMixBuildHasher {
+    /// Create a builder with a random seed, see `new`.
+    fn default() -> SplitMixBuildHasher {
+        SplitMixBuildHasher::new()
+    }
+}
+
+impl BuildHasher for SplitMixBuildHasher {
+    type Hasher = SplitMixHasher;
+
+    fn build_hasher(&self) -> SplitMixHasher {
+        SplitMixHasher::with_seed(self.seed)
+    }
+}
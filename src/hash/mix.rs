@@ -0,0 +1,132 @@
+/// A bijective mixing function on 64-bit words.
+pub trait Finalizer {
+    /// Mix the bits of `x`.
+    fn mix(x: u64) -> u64;
+
+    /// Invert `mix`, such that `unmix(mix(x)) == x`.
+    fn unmix(x: u64) -> u64;
+}
+
+/// A finalizer alternating xorshifts and multiplications.
+///
+/// It computes `x ^= x >> S1; x *= M1; x ^= x >> S2; x *= M2; x ^= x >> S3`.
+/// The shifts must be between 1 and 63 and the multipliers must be odd, so
+/// that every step is invertible.  Other constants fail to compile.
+///
+/// ```compile_fail
+/// # extern crate xoroshiro;
+/// # fn main() {
+/// use xoroshiro::hash::{Finalizer, XorShiftMultiply};
+///
+/// // The second multiplier is even.
+/// XorShiftMultiply::<33, 0xff51afd7ed558ccd, 33, 0xc4ceb9fe1a85ec54, 33>::mix(1);
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct XorShiftMultiply<const S1: u32, const M1: u64, const S2: u32, const M2: u64,
+                            const S3: u32>;
+
+impl<const S1: u32, const M1: u64, const S2: u32, const M2: u64, const S3: u32>
+    XorShiftMultiply<S1, M1, S2, M2, S3>
+{
+    /// The inverse of `M1`, which also validates the constants.
+    const INV1: u64 = {
+        assert!(S1 > 0 && S1 < 64 && S2 > 0 && S2 < 64 && S3 > 0 && S3 < 64,
+            "the shifts must be between 1 and 63");
+        assert!(M1 & 1 == 1 && M2 & 1 == 1, "the multipliers must be odd");
+        mod_inverse(M1)
+    };
+
+    /// The inverse of `M2`.
+    const INV2: u64 = mod_inverse(M2);
+}
+
+impl<const S1: u32, const M1: u64, const S2: u32, const M2: u64, const S3: u32> Finalizer
+    for XorShiftMultiply<S1, M1, S2, M2, S3>
+{
+    #[inline]
+    fn mix(mut x: u64) -> u64 {
+        let _ = Self::INV1;
+        x = (x ^ (x >> S1)).wrapping_mul(M1);
+        x = (x ^ (x >> S2)).wrapping_mul(M2);
+        x ^ (x >> S3)
+    }
+
+    #[inline]
+    fn unmix(mut x: u64) -> u64 {
+        x = unxorshift(x, S3).wrapping_mul(Self::INV2);
+        x = unxorshift(x, S2).wrapping_mul(Self::INV1);
+        unxorshift(x, S1)
+    }
+}
+
+/// The finalizer `fmix64` of MurmurHash3 by Austin Appleby.
+///
+/// V8 uses it for seeding and `SplittableRandom` for deriving gammas.
+pub type Murmur3 = XorShiftMultiply<33, 0xff51afd7ed558ccd, 33, 0xc4ceb9fe1a85ec53, 33>;
+
+/// Variant `Mix01` of the MurmurHash3 finalizer by David Stafford.
+pub type Stafford01 = XorShiftMultiply<31, 0x7fb5d329728ea185, 27, 0x81dadef4bc2dd44d, 33>;
+
+/// Variant `Mix04` of the MurmurHash3 finalizer by David Stafford.
+///
+/// Its upper 32 bits are the 32-bit output of `SplittableRandom`.
+pub type Stafford04 = XorShiftMultiply<33, 0x62a9d9ed799705f5, 28, 0xcb24d0a5c88c35b3, 32>;
+
+/// Variant `Mix13` of the MurmurHash3 finalizer by David Stafford.
+///
+/// This is the output function of `SplitMix64`, also available as `mix64`.
+pub type Stafford13 = XorShiftMultiply<30, 0xbf58476d1ce4e5b9, 27, 0x94d049bb133111eb, 31>;
+
+/// The `moremur` finalizer by Pelle Evensen.
+pub type Moremur = XorShiftMultiply<27, 0x3c79ac492ba7b653, 33, 0x1c69b3f74ac4ae35, 27>;
+
+/// Mix the bits of `x` with the output function of `SplitMix64`.
+///
+/// This is `Stafford13::mix`, a bijection with the inverse `unmix64`.
+///
+/// ```
+/// # extern crate xoroshiro;
+/// # fn main() {
+/// use xoroshiro::hash::{mix64, unmix64};
+///
+/// let id = 12345;
+/// let scrambled = mix64(id);
+/// assert_eq!(unmix64(scrambled), id);
+/// # }
+/// ```
+#[inline]
+pub fn mix64(x: u64) -> u64 {
+    Stafford13::mix(x)
+}
+
+/// Invert `mix64`.
+#[inline]
+pub fn unmix64(x: u64) -> u64 {
+    Stafford13::unmix(x)
+}
+
+/// Invert `x ^= x >> s` for `0 < s < 64`.
+#[inline]
+fn unxorshift(mut x: u64, s: u32) -> u64 {
+    // The inverse of `1 + T^s` is `(1 + T^s)(1 + T^2s)(1 + T^4s)...`.
+    let mut k = s;
+    while k < 64 {
+        x ^= x >> k;
+        k *= 2;
+    }
+    x
+}
+
+/// Compute the inverse of the odd integer `a` modulo 2^64.
+pub(crate) const fn mod_inverse(a: u64) -> u64 {
+    // Every odd `a` is its own inverse modulo 2^3, each Newton iteration
+    // doubles the number of correct bits.
+    let mut x = a;
+    let mut i = 0;
+    while i < 5 {
+        x = x.wrapping_mul(2u64.wrapping_sub(a.wrapping_mul(x)));
+        i += 1;
+    }
+    x
+}
@@ -0,0 +1,14 @@
+//! Bijective 64-bit mixing functions and hashers.
+//!
+//! The finalizers scramble the bits of a 64-bit word such that every input
+//! bit affects every output bit.  They are bijections with an exact inverse,
+//! so they can be used to hash integers and to scramble identifiers
+//! reversibly.  `SplitMixHasher` builds a `Hasher` for hash maps on them.
+
+mod hasher;
+mod mix;
+
+pub use self::hasher::{SplitMixHasher, SplitMixBuildHasher};
+pub use self::mix::{mix64, unmix64, Finalizer, XorShiftMultiply,
+                    Murmur3, Stafford01, Stafford04, Stafford13, Moremur};
+pub(crate) use self::mix::mod_inverse;
@@ -5,13 +5,75 @@
 //! splitmix64](http://xoroshiro.di.unimi.it) random number generators.
 //!
 //! It is recommended to use `XoroShiro128` unless you need a period larger
-//! than 2^128 - 1, where `XorShift1024` with a period of 2^1024 - 1 is more
-//! appropriate. `SplitMix64` is only used to initialize the other generators,
-//! it should not be used directly, unless you are sure that a period of
-//! 2^64 - 1 is enough.
+//! than 2^128 - 1, where `Xoshiro512StarStar` with a period of 2^512 - 1 or
+//! `XoroShiro1024StarStar` with a period of 2^1024 - 1 are more appropriate.
+//! They supersede `XorShift1024`. `SplitMix64` is only used to initialize the
+//! other generators, it should not be used directly, unless you are sure that
+//! a period of 2^64 - 1 is enough.
+//!
+//! `SplitMix64` supports splitting into independent generators, random access
+//! to its outputs and, as `AtomicSplitMix64`, lock-free sharing between
+//! threads.  `RandomPermutation` uses it to visit `0..n` in a random order
+//! without storing a shuffled array.
+//!
+//! `XoroShiro128` uses the original parameters of xoroshiro128+.  The
+//! parameters used by the current reference implementation are available as
+//! `XoroShiro128Plus2018`, which produces a different stream.
+//!
+//! All generators combine a linear engine (for example `XoroShiro128Engine` or
+//! `Xoshiro256Engine`) with an output scrambler (`Plus`, `Star`, `StarStar`,
+//! `PlusPlus`, `StarPhi`) using `Scrambled`.  Besides the generators above,
+//! this provides `XoroShiro128Star`, `XoroShiro128StarStar`,
+//! `XoroShiro128PlusPlus`, `Xoshiro256Plus`, `Xoshiro256PlusPlus` and
+//! `Xoshiro256StarStar`.
+//!
+//! The generators provide `next_f64` and `next_f32` for floats in [0, 1), and
+//! `next_f64_open`, `next_f64_closed` and `next_f64_open_closed` for the other
+//! intervals.  They only use the upper bits of the outputs.
+//! `next_f64_full_precision` can return every double in [0, 1), including
+//! values below 2^-53, and `uniform_f64` extends this to a range [lo, hi).
+//! For integers, `gen_below_u32`, `gen_below_u64`, `gen_below_u128` and
+//! `gen_range_i64` use Lemire's unbiased multiply-shift method on the upper
+//! bits.  `BitBuffer` wraps a generator to serve booleans and small integers
+//! from the unused bits of its outputs, and `ByteStream` keeps the unused
+//! bytes, so that the bytes do not depend on the size of the requests.  Over
+//! a generator implementing `Advance`, like `SplitMix64`, it can `seek` to
+//! any byte of the stream.
+//!
+//! On 32-bit targets, `XoroShiro64Star`, `XoroShiro64StarStar`,
+//! `Xoshiro128StarStar` and `Xoshiro128PlusPlus` avoid 64-bit arithmetic.  They
+//! produce 32 bits at a time, `next_u64` is composed from two outputs.
+//!
+//! For reproducing the random numbers of other software, `XorShift128Plus`
+//! implements the xorshift128+ generator used by JavaScript engines, and
+//! `V8MathRandom` reproduces `Math.random()` of V8.  `ErlangExrop` and
+//! `ErlangExsss` reproduce the `exrop` and `exsss` algorithms of Erlang's
+//! `rand` module, which use 58-bit words.  `LuaRandom` and `DotNetRandom`
+//! reproduce the seeding, float and integer range conventions of Lua 5.4's
+//! `math.random` and .NET's `System.Random` on top of xoshiro256**.
+//! `SplittableRandom` reproduces `java.util.SplittableRandom`, including
+//! `split`.
+//!
+//! For experimenting with other constants of the xoroshiro128 engine,
+//! `XoroShiro128ParamsCustom` accepts them as const generic parameters.  Only
+//! constants giving the full period of 2^128 - 1 are accepted, which can be
+//! checked at run time with `check_xoroshiro128_params`.
+//!
+//! The `hash` module provides the output function of `SplitMix64` as the
+//! bijective hash `mix64` with its inverse `unmix64`, along with other
+//! finalizers implementing the `Finalizer` trait.  `SplitMixHasher` and
+//! `SplitMixBuildHasher` use `mix64` for fast, seedable hashing in hash maps.
+//!
+//! If the `serde` feature is enabled, the generators and the seed types
+//! implement `Serialize` and `Deserialize`.  The exact internal state is
+//! serialized, and invalid states are rejected when deserializing.
 
 extern crate rand_core;
 extern crate byteorder;
+#[cfg(feature = "serde")]
+extern crate serde;
 
+/// Bijective 64-bit mixing functions and hashers.
+pub mod hash;
 /// Pseudo-random number generators.
 pub mod rng;
@@ -0,0 +1,122 @@
+use rand_core;
+use rand_core::RngCore;
+
+use super::scrambled::fill_bytes_via_u64;
+
+/// An adapter serving random bits from a buffer of unused output bits.
+///
+/// The outputs of `next_u64()` are consumed as a stream of bits, starting
+/// with the most significant bit of each output.  `next_bool` uses a single
+/// bit, `next_bits(k)` uses `k` bits and `next_u32` uses 32 bits, so 64
+/// booleans or two `u32` only cost one call of the underlying generator.  For
+/// generators like `XoroShiro128`, whose lowest bits are weaker, the upper
+/// bits are thus used first.
+///
+/// The result only depends on the sequence of requests, not on the platform:
+/// reading `j` and then `k` bits gives the same bits as reading `j + k` bits
+/// at once.
+///
+/// ```
+/// # extern crate rand;
+/// # extern crate xoroshiro;
+/// # fn main() {
+/// use rand::RngCore;
+/// use xoroshiro::rng::{BitBuffer, XoroShiro128};
+///
+/// let mut rng = BitBuffer::new(XoroShiro128::from_seed_u64(0));
+/// let heads = (0..64).filter(|_| rng.next_bool()).count();
+/// // The heads are the set bits of the first output.
+/// assert_eq!(heads as u32, XoroShiro128::from_seed_u64(0).next_u64().count_ones());
+/// // All coin flips came from a single output.
+/// assert_eq!(rng.buffered(), 0);
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct BitBuffer<R> {
+    rng: R,
+    /// The unused bits, aligned to the most significant bit.
+    bits: u64,
+    len: u32,
+}
+
+impl<R: RngCore> BitBuffer<R> {
+    /// Create an adapter with an empty buffer.
+    pub fn new(rng: R) -> BitBuffer<R> {
+        BitBuffer { rng, bits: 0, len: 0 }
+    }
+
+    /// Return a reference to the underlying generator.
+    pub fn get_ref(&self) -> &R {
+        &self.rng
+    }
+
+    /// Return the underlying generator, discarding the buffered bits.
+    pub fn into_inner(self) -> R {
+        self.rng
+    }
+
+    /// Return the number of buffered bits, which is less than 64.
+    pub fn buffered(&self) -> u32 {
+        self.len
+    }
+
+    /// Return `k` random bits in the lower bits of the result.
+    ///
+    /// The upper `64 - k` bits of the result are zero.  This panics if `k`
+    /// is larger than 64.
+    #[inline]
+    pub fn next_bits(&mut self, k: u32) -> u64 {
+        assert!(k <= 64, "BitBuffer::next_bits called with more than 64 bits.");
+        if k <= self.len {
+            let r = upper_bits(self.bits, k);
+            self.bits = self.bits.checked_shl(k).unwrap_or(0);
+            self.len -= k;
+            return r;
+        }
+        // Use the buffered bits as the upper bits of the result and complete
+        // them with the upper bits of a new output.
+        let high = upper_bits(self.bits, self.len);
+        let need = k - self.len;
+        let x = self.rng.next_u64();
+        self.bits = x.checked_shl(need).unwrap_or(0);
+        self.len = 64 - need;
+        high.checked_shl(need).unwrap_or(0) | upper_bits(x, need)
+    }
+
+    /// Return a random boolean using a single bit.
+    #[inline]
+    pub fn next_bool(&mut self) -> bool {
+        self.next_bits(1) == 1
+    }
+}
+
+/// Return the upper `k` bits of `x`, for `k <= 64`.
+#[inline]
+fn upper_bits(x: u64, k: u32) -> u64 {
+    x.checked_shr(64 - k).unwrap_or(0)
+}
+
+impl<R: RngCore> RngCore for BitBuffer<R> {
+    /// Return the next 32 bits of the stream.
+    #[inline]
+    fn next_u32(&mut self) -> u32 {
+        self.next_bits(32) as u32
+    }
+
+    /// Return the next 64 bits of the stream.
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        self.next_bits(64)
+    }
+
+    #[inline]
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        fill_bytes_via_u64(self, dest)
+    }
+
+    #[inline]
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
@@ -0,0 +1,95 @@
+//! Unbiased bounded integers using Lemire's multiply-shift rejection.
+//!
+//! A random word `x` is mapped to `x * n / 2^bits`, the upper half of the
+//! product, which only depends on the upper bits of `x`.  The lower half of
+//! the product tells whether `x` falls into one of the `2^bits mod n` values
+//! that would bias the result, in which case it is rejected.  The modulo is
+//! only computed when the lower half is smaller than `n`, so it is rarely
+//! needed.  See "Fast Random Integer Generation in an Interval" by Daniel
+//! Lemire.
+
+use rand_core::RngCore;
+
+/// Return a uniform integer in [0, n) from `next_u32()`.
+///
+/// This panics if `n` is 0.
+#[inline]
+pub(crate) fn below_u32<R: RngCore + ?Sized>(rng: &mut R, n: u32) -> u32 {
+    assert!(n > 0, "gen_below_u32 called with an empty range.");
+    let mut m = u64::from(rng.next_u32()) * u64::from(n);
+    if (m as u32) < n {
+        let threshold = n.wrapping_neg() % n;
+        while (m as u32) < threshold {
+            m = u64::from(rng.next_u32()) * u64::from(n);
+        }
+    }
+    (m >> 32) as u32
+}
+
+/// Return a uniform integer in [0, n) from `next_u64()`.
+///
+/// This panics if `n` is 0.
+#[inline]
+pub(crate) fn below_u64<R: RngCore + ?Sized>(rng: &mut R, n: u64) -> u64 {
+    assert!(n > 0, "gen_below_u64 called with an empty range.");
+    let mut m = u128::from(rng.next_u64()) * u128::from(n);
+    if (m as u64) < n {
+        let threshold = n.wrapping_neg() % n;
+        while (m as u64) < threshold {
+            m = u128::from(rng.next_u64()) * u128::from(n);
+        }
+    }
+    (m >> 64) as u64
+}
+
+/// Return a uniform integer in [0, n) from two calls to `next_u64()`, the
+/// first one giving the upper 64 bits.
+///
+/// This panics if `n` is 0.
+pub(crate) fn below_u128<R: RngCore + ?Sized>(rng: &mut R, n: u128) -> u128 {
+    assert!(n > 0, "gen_below_u128 called with an empty range.");
+    let (mut high, mut low) = mul_wide(next_u128(rng), n);
+    if low < n {
+        let threshold = n.wrapping_neg() % n;
+        while low < threshold {
+            let (h, l) = mul_wide(next_u128(rng), n);
+            high = h;
+            low = l;
+        }
+    }
+    high
+}
+
+/// Return a uniform integer in [lo, hi).
+///
+/// This panics unless `lo < hi`.
+#[inline]
+pub(crate) fn range_i64<R: RngCore + ?Sized>(rng: &mut R, lo: i64, hi: i64) -> i64 {
+    assert!(lo < hi, "gen_range_i64 called with an empty range.");
+    let n = hi.wrapping_sub(lo) as u64;
+    lo.wrapping_add(below_u64(rng, n) as i64)
+}
+
+#[inline]
+fn next_u128<R: RngCore + ?Sized>(rng: &mut R) -> u128 {
+    let high = u128::from(rng.next_u64());
+    let low = u128::from(rng.next_u64());
+    (high << 64) | low
+}
+
+/// Return the upper and the lower half of the 256-bit product `a * b`.
+#[inline]
+fn mul_wide(a: u128, b: u128) -> (u128, u128) {
+    const MASK: u128 = (1 << 64) - 1;
+    let (a1, a0) = (a >> 64, a & MASK);
+    let (b1, b0) = (b >> 64, b & MASK);
+    let p00 = a0 * b0;
+    let p01 = a0 * b1;
+    let p10 = a1 * b0;
+    let p11 = a1 * b1;
+    // The middle sum has at most 66 bits.
+    let middle = (p00 >> 64) + (p01 & MASK) + (p10 & MASK);
+    let low = (middle << 64) | (p00 & MASK);
+    let high = p11 + (p01 >> 64) + (p10 >> 64) + (middle >> 64);
+    (high, low)
+}
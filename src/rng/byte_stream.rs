@@ -0,0 +1,151 @@
+use rand_core;
+use rand_core::RngCore;
+use byteorder::{LittleEndian, ByteOrder};
+
+/// A generator which can skip outputs without computing them.
+pub trait Advance {
+    /// Advance the generator by `n` outputs, equivalently to `n` calls to
+    /// `next_u64()`.
+    ///
+    /// Advancing by `2^64 - n` outputs goes back by `n` outputs.
+    fn advance(&mut self, n: u64);
+}
+
+/// An adapter turning the outputs of a generator into a continuous stream of
+/// bytes.
+///
+/// Each output of `next_u64()` gives 8 bytes in little-endian order, like
+/// `fill_bytes` of the generators in this crate.  Unlike their `fill_bytes`,
+/// the unused bytes of the last output are kept for the next call, so the
+/// bytes do not depend on how the requests are chunked: filling 3 and then 5
+/// bytes gives the same bytes as filling 8 bytes at once.  `next_u32` and
+/// `next_u64` also take their bytes from the stream.
+///
+/// ```
+/// # extern crate rand;
+/// # extern crate xoroshiro;
+/// # fn main() {
+/// use rand::RngCore;
+/// use xoroshiro::rng::{ByteStream, XoroShiro128};
+///
+/// let mut a = ByteStream::new(XoroShiro128::from_seed_u64(0));
+/// let mut b = a.clone();
+/// let mut x = [0; 8];
+/// let mut y = [0; 8];
+/// a.fill_bytes(&mut x);
+/// b.fill_bytes(&mut y[..3]);
+/// b.fill_bytes(&mut y[3..]);
+/// assert_eq!(x, y);
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ByteStream<R> {
+    rng: R,
+    /// The last output, whose last `len` bytes are unused.
+    buf: [u8; 8],
+    len: usize,
+    /// The number of outputs taken from `rng`, modulo 2^64.
+    outputs: u64,
+}
+
+impl<R: RngCore> ByteStream<R> {
+    /// Create a stream starting with the next output of `rng`.
+    pub fn new(rng: R) -> ByteStream<R> {
+        ByteStream { rng, buf: [0; 8], len: 0, outputs: 0 }
+    }
+
+    /// Return a reference to the underlying generator.
+    pub fn get_ref(&self) -> &R {
+        &self.rng
+    }
+
+    /// Return the underlying generator, discarding the buffered bytes.
+    pub fn into_inner(self) -> R {
+        self.rng
+    }
+
+    /// Return the number of bytes taken from the stream, modulo 2^64.
+    pub fn position(&self) -> u64 {
+        self.outputs.wrapping_mul(8).wrapping_sub(self.len as u64)
+    }
+
+    /// Return the number of buffered bytes, which is less than 8.
+    pub fn buffered(&self) -> usize {
+        self.len
+    }
+}
+
+impl<R: RngCore + Advance> ByteStream<R> {
+    /// Move to the byte `offset` of the stream, counted from the creation of
+    /// the stream.
+    ///
+    /// This takes constant time if `Advance::advance` does, as for
+    /// `SplitMix64`, both forward and backward.
+    pub fn seek(&mut self, offset: u64) {
+        let target = offset / 8;
+        self.rng.advance(target.wrapping_sub(self.outputs));
+        self.outputs = target;
+        let skip = (offset % 8) as usize;
+        if skip == 0 {
+            self.len = 0;
+        } else {
+            self.refill();
+            self.len = 8 - skip;
+        }
+    }
+}
+
+impl<R: RngCore> ByteStream<R> {
+    /// Buffer the next output of the generator.
+    #[inline]
+    fn refill(&mut self) {
+        LittleEndian::write_u64(&mut self.buf, self.rng.next_u64());
+        self.outputs = self.outputs.wrapping_add(1);
+    }
+}
+
+impl<R: RngCore> RngCore for ByteStream<R> {
+    #[inline]
+    fn next_u32(&mut self) -> u32 {
+        let mut bytes = [0; 4];
+        self.fill_bytes(&mut bytes);
+        LittleEndian::read_u32(&bytes)
+    }
+
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        if self.len == 0 {
+            self.outputs = self.outputs.wrapping_add(1);
+            return self.rng.next_u64();
+        }
+        let mut bytes = [0; 8];
+        self.fill_bytes(&mut bytes);
+        LittleEndian::read_u64(&bytes)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        let n = self.len.min(dest.len());
+        let (head, tail) = dest.split_at_mut(n);
+        let start = 8 - self.len;
+        head.copy_from_slice(&self.buf[start..start + n]);
+        self.len -= n;
+
+        let mut chunks = tail.chunks_exact_mut(8);
+        for chunk in &mut chunks {
+            LittleEndian::write_u64(chunk, self.rng.next_u64());
+            self.outputs = self.outputs.wrapping_add(1);
+        }
+        let rest = chunks.into_remainder();
+        if !rest.is_empty() {
+            self.refill();
+            rest.copy_from_slice(&self.buf[..rest.len()]);
+            self.len = 8 - rest.len();
+        }
+    }
+
+    #[inline]
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
@@ -0,0 +1,226 @@
+//! A compact, self-describing binary format for generator states.
+//!
+//! A checkpoint consists of
+//!
+//! - the magic bytes `XRNG`,
+//! - the format version as one byte,
+//! - the algorithm identifier as one byte,
+//! - the length of the state in bytes as a little-endian `u16`,
+//! - the state words as little-endian `u64`s,
+//! - the CRC-32 (IEEE) of all preceding bytes as a little-endian `u32`.
+
+use byteorder::{LittleEndian, ByteOrder};
+
+use super::CheckpointError;
+
+const MAGIC: [u8; 4] = *b"XRNG";
+const VERSION: u8 = 1;
+const HEADER_LEN: usize = 8;
+const CRC_LEN: usize = 4;
+
+/// The algorithm of a generator stored in a checkpoint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Algorithm {
+    /// xoroshiro128+, see `XoroShiro128`.
+    XoroShiro128Plus,
+    /// xorshift1024*φ, see `XorShift1024`.
+    XorShift1024StarPhi,
+    /// splitmix64, see `SplitMix64`.
+    SplitMix64,
+    /// xoroshiro128+ with the 2018 parameters, see `XoroShiro128Plus2018`.
+    XoroShiro128Plus2018,
+    /// xoroshiro128*, see `XoroShiro128Star`.
+    XoroShiro128Star,
+    /// xoroshiro128**, see `XoroShiro128StarStar`.
+    XoroShiro128StarStar,
+    /// xoroshiro128++, see `XoroShiro128PlusPlus`.
+    XoroShiro128PlusPlus,
+    /// xoshiro256+, see `Xoshiro256Plus`.
+    Xoshiro256Plus,
+    /// xoshiro256++, see `Xoshiro256PlusPlus`.
+    Xoshiro256PlusPlus,
+    /// xoshiro256**, see `Xoshiro256StarStar`.
+    Xoshiro256StarStar,
+    /// xoroshiro64*, see `XoroShiro64Star`.
+    XoroShiro64Star,
+    /// xoroshiro64**, see `XoroShiro64StarStar`.
+    XoroShiro64StarStar,
+    /// xoshiro128**, see `Xoshiro128StarStar`.
+    Xoshiro128StarStar,
+    /// xoshiro128++, see `Xoshiro128PlusPlus`.
+    Xoshiro128PlusPlus,
+    /// xoshiro512**, see `Xoshiro512StarStar`.
+    Xoshiro512StarStar,
+    /// xoshiro512++, see `Xoshiro512PlusPlus`.
+    Xoshiro512PlusPlus,
+    /// xoroshiro1024**, see `XoroShiro1024StarStar`.
+    XoroShiro1024StarStar,
+    /// xoroshiro1024++, see `XoroShiro1024PlusPlus`.
+    XoroShiro1024PlusPlus,
+    /// xorshift128+, see `XorShift128Plus`.
+    XorShift128Plus,
+}
+
+/// A generator with a fixed checkpoint algorithm.
+///
+/// This determines the algorithm identifier used by checkpoints and the name
+/// used by state strings.
+pub trait KnownAlgorithm {
+    /// The algorithm of the generator.
+    const ALGORITHM: Algorithm;
+}
+
+impl Algorithm {
+    fn tag(self) -> u8 {
+        match self {
+            Algorithm::XoroShiro128Plus => 1,
+            Algorithm::XorShift1024StarPhi => 2,
+            Algorithm::SplitMix64 => 3,
+            Algorithm::XoroShiro128Plus2018 => 4,
+            Algorithm::XoroShiro128Star => 5,
+            Algorithm::XoroShiro128StarStar => 6,
+            Algorithm::XoroShiro128PlusPlus => 7,
+            Algorithm::Xoshiro256Plus => 8,
+            Algorithm::Xoshiro256PlusPlus => 9,
+            Algorithm::Xoshiro256StarStar => 10,
+            Algorithm::XoroShiro64Star => 11,
+            Algorithm::XoroShiro64StarStar => 12,
+            Algorithm::Xoshiro128StarStar => 13,
+            Algorithm::Xoshiro128PlusPlus => 14,
+            Algorithm::Xoshiro512StarStar => 15,
+            Algorithm::Xoshiro512PlusPlus => 16,
+            Algorithm::XoroShiro1024StarStar => 17,
+            Algorithm::XoroShiro1024PlusPlus => 18,
+            Algorithm::XorShift128Plus => 19,
+        }
+    }
+
+    fn from_tag(tag: u8) -> Option<Algorithm> {
+        match tag {
+            1 => Some(Algorithm::XoroShiro128Plus),
+            2 => Some(Algorithm::XorShift1024StarPhi),
+            3 => Some(Algorithm::SplitMix64),
+            4 => Some(Algorithm::XoroShiro128Plus2018),
+            5 => Some(Algorithm::XoroShiro128Star),
+            6 => Some(Algorithm::XoroShiro128StarStar),
+            7 => Some(Algorithm::XoroShiro128PlusPlus),
+            8 => Some(Algorithm::Xoshiro256Plus),
+            9 => Some(Algorithm::Xoshiro256PlusPlus),
+            10 => Some(Algorithm::Xoshiro256StarStar),
+            11 => Some(Algorithm::XoroShiro64Star),
+            12 => Some(Algorithm::XoroShiro64StarStar),
+            13 => Some(Algorithm::Xoshiro128StarStar),
+            14 => Some(Algorithm::Xoshiro128PlusPlus),
+            15 => Some(Algorithm::Xoshiro512StarStar),
+            16 => Some(Algorithm::Xoshiro512PlusPlus),
+            17 => Some(Algorithm::XoroShiro1024StarStar),
+            18 => Some(Algorithm::XoroShiro1024PlusPlus),
+            19 => Some(Algorithm::XorShift128Plus),
+            _ => None,
+        }
+    }
+
+    /// Return the name of the algorithm, as used by state strings.
+    pub fn name(self) -> &'static str {
+        match self {
+            Algorithm::XoroShiro128Plus => "xoroshiro128+",
+            Algorithm::XorShift1024StarPhi => "xorshift1024*phi",
+            Algorithm::SplitMix64 => "splitmix64",
+            Algorithm::XoroShiro128Plus2018 => "xoroshiro128+2018",
+            Algorithm::XoroShiro128Star => "xoroshiro128*",
+            Algorithm::XoroShiro128StarStar => "xoroshiro128**",
+            Algorithm::XoroShiro128PlusPlus => "xoroshiro128++",
+            Algorithm::Xoshiro256Plus => "xoshiro256+",
+            Algorithm::Xoshiro256PlusPlus => "xoshiro256++",
+            Algorithm::Xoshiro256StarStar => "xoshiro256**",
+            Algorithm::XoroShiro64Star => "xoroshiro64*",
+            Algorithm::XoroShiro64StarStar => "xoroshiro64**",
+            Algorithm::Xoshiro128StarStar => "xoshiro128**",
+            Algorithm::Xoshiro128PlusPlus => "xoshiro128++",
+            Algorithm::Xoshiro512StarStar => "xoshiro512**",
+            Algorithm::Xoshiro512PlusPlus => "xoshiro512++",
+            Algorithm::XoroShiro1024StarStar => "xoroshiro1024**",
+            Algorithm::XoroShiro1024PlusPlus => "xoroshiro1024++",
+            Algorithm::XorShift128Plus => "xorshift128+",
+        }
+    }
+
+    /// Return the algorithm stored in a checkpoint.
+    ///
+    /// This validates the header and the checksum, but not the state itself.
+    pub fn from_checkpoint_bytes(bytes: &[u8]) -> Result<Algorithm, CheckpointError> {
+        parse(bytes).map(|(algorithm, _)| algorithm)
+    }
+}
+
+/// Compute the CRC-32 (IEEE 802.3) checksum of `bytes`.
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &b in bytes {
+        crc ^= u32::from(b);
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xedb88320 & mask);
+        }
+    }
+    !crc
+}
+
+/// Encode the state words of a generator as a checkpoint.
+pub(crate) fn encode(algorithm: Algorithm, state: &[u64]) -> Vec<u8> {
+    let state_len = state.len() * 8;
+    debug_assert!(state_len <= u16::MAX as usize);
+    let mut bytes = vec![0; HEADER_LEN + state_len + CRC_LEN];
+    bytes[..4].copy_from_slice(&MAGIC);
+    bytes[4] = VERSION;
+    bytes[5] = algorithm.tag();
+    LittleEndian::write_u16(&mut bytes[6..8], state_len as u16);
+    LittleEndian::write_u64_into(state, &mut bytes[HEADER_LEN..HEADER_LEN + state_len]);
+    let crc = crc32(&bytes[..HEADER_LEN + state_len]);
+    LittleEndian::write_u32(&mut bytes[HEADER_LEN + state_len..], crc);
+    bytes
+}
+
+/// Validate a checkpoint and return its algorithm and raw state bytes.
+fn parse(bytes: &[u8]) -> Result<(Algorithm, &[u8]), CheckpointError> {
+    if bytes.len() < HEADER_LEN + CRC_LEN {
+        return Err(CheckpointError::InvalidLength);
+    }
+    if bytes[..4] != MAGIC {
+        return Err(CheckpointError::BadMagic);
+    }
+    if bytes[4] != VERSION {
+        return Err(CheckpointError::UnsupportedVersion(bytes[4]));
+    }
+    let state_len = LittleEndian::read_u16(&bytes[6..8]) as usize;
+    if bytes.len() != HEADER_LEN + state_len + CRC_LEN {
+        return Err(CheckpointError::InvalidLength);
+    }
+    let (data, crc) = bytes.split_at(HEADER_LEN + state_len);
+    if crc32(data) != LittleEndian::read_u32(crc) {
+        return Err(CheckpointError::ChecksumMismatch);
+    }
+    let algorithm = Algorithm::from_tag(bytes[5])
+        .ok_or(CheckpointError::UnknownAlgorithm(bytes[5]))?;
+    Ok((algorithm, &data[HEADER_LEN..]))
+}
+
+/// Decode the state words of a generator of the given algorithm from a
+/// checkpoint.
+pub(crate) fn decode(bytes: &[u8], algorithm: Algorithm, state: &mut [u64])
+    -> Result<(), CheckpointError>
+{
+    let (found, data) = parse(bytes)?;
+    if found != algorithm {
+        return Err(CheckpointError::AlgorithmMismatch {
+            expected: algorithm,
+            found,
+        });
+    }
+    if data.len() != state.len() * 8 {
+        return Err(CheckpointError::InvalidLength);
+    }
+    LittleEndian::read_u64_into(data, state);
+    Ok(())
+}
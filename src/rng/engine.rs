@@ -0,0 +1,212 @@
+use std::fmt;
+
+use byteorder::{LittleEndian, ByteOrder};
+use rand_core::RngCore;
+
+use super::{SplitMix64, StateError};
+
+/// A state word of an engine.
+///
+/// This is implemented for `u32` and `u64`.
+pub trait Word: Copy + Default + Eq + fmt::Debug + fmt::LowerHex + 'static {
+    /// The number of bits of the word.
+    const BITS: u32;
+
+    /// Return the lowest bits of `x`.
+    fn from_u64(x: u64) -> Self;
+
+    /// Return the word zero-extended to 64 bits.
+    fn to_u64(self) -> u64;
+
+    /// Read little-endian words from `bytes` into `dst`.
+    fn read_le(bytes: &[u8], dst: &mut [Self]);
+}
+
+impl Word for u32 {
+    const BITS: u32 = 32;
+
+    #[inline]
+    fn from_u64(x: u64) -> u32 {
+        x as u32
+    }
+
+    #[inline]
+    fn to_u64(self) -> u64 {
+        u64::from(self)
+    }
+
+    fn read_le(bytes: &[u8], dst: &mut [u32]) {
+        LittleEndian::read_u32_into(bytes, dst);
+    }
+}
+
+impl Word for u64 {
+    const BITS: u32 = 64;
+
+    #[inline]
+    fn from_u64(x: u64) -> u64 {
+        x
+    }
+
+    #[inline]
+    fn to_u64(self) -> u64 {
+        self
+    }
+
+    fn read_le(bytes: &[u8], dst: &mut [u64]) {
+        LittleEndian::read_u64_into(bytes, dst);
+    }
+}
+
+/// Pack words into little-endian 64-bit words.
+///
+/// The number of bits of `words` must be a multiple of 64.
+pub(crate) fn pack_words<W: Word>(words: &[W]) -> Vec<u64> {
+    let per = (64 / W::BITS) as usize;
+    debug_assert!(words.len().is_multiple_of(per));
+    let mut packed = vec![0; words.len() / per];
+    for (i, &w) in words.iter().enumerate() {
+        packed[i / per] |= w.to_u64() << ((i % per) as u32 * W::BITS);
+    }
+    packed
+}
+
+/// Unpack little-endian 64-bit words into `words`, the inverse of
+/// `pack_words`.
+pub(crate) fn unpack_words<W: Word>(packed: &[u64], words: &mut [W]) {
+    let per = (64 / W::BITS) as usize;
+    debug_assert!(words.len() == packed.len() * per);
+    for (i, w) in words.iter_mut().enumerate() {
+        *w = W::from_u64(packed[i / per] >> ((i % per) as u32 * W::BITS));
+    }
+}
+
+/// The linear state transition of a generator.
+///
+/// An engine only advances its state.  The output of a generator is computed
+/// from the state by a `Scrambler`, see `Scrambled`.
+pub trait Engine: Clone {
+    /// The seed type used by `SeedableRng`.
+    ///
+    /// The seed bytes are read as little-endian state words.
+    type Seed: Default + AsMut<[u8]>;
+
+    /// The type of the state words.
+    type Word: Word;
+
+    /// The typed state returned by `state` and accepted by `from_state`.
+    type State;
+
+    /// The number of state words.
+    ///
+    /// The state has a multiple of 64 bits.
+    const WORDS: usize;
+
+    /// Whether the state includes an index into the state words, in addition
+    /// to the state words themselves.
+    const INDEXED: bool;
+
+    /// Create an engine with a constant state.
+    ///
+    /// By default, this uses the same state as seeding with `SplitMix64`
+    /// seeded with zero.
+    fn new_unseeded() -> Self {
+        let mut rng = SplitMix64::from_seed_u64(0);
+        let packed: Vec<u64> = (0..Self::u64_words()).map(|_| rng.next_u64()).collect();
+        let mut words = vec![Self::Word::default(); Self::WORDS];
+        unpack_words(&packed, &mut words);
+        Self::from_words(&words, 0).unwrap()
+    }
+
+    /// The number of bits of the state words, as a number of 64-bit words.
+    fn u64_words() -> usize {
+        Self::WORDS * Self::Word::BITS as usize / 64
+    }
+
+    /// Create an engine from its state words and index.
+    ///
+    /// `words` must have exactly `WORDS` elements.  For engines which are not
+    /// indexed, `index` must be zero.  This fails if the state words are
+    /// entirely zero or if the index is out of range.
+    fn from_words(words: &[Self::Word], index: usize) -> Result<Self, StateError>;
+
+    /// Return the state words.
+    fn words(&self) -> &[Self::Word];
+
+    /// Return the index into the state words, which is always zero for
+    /// engines which are not indexed.
+    fn index(&self) -> usize;
+
+    /// Return the typed state of the engine.
+    fn state(&self) -> Self::State;
+
+    /// Create an engine from a state previously returned by `state`.
+    fn from_state(state: Self::State) -> Result<Self, StateError>;
+
+    /// Advance the state by one step.
+    fn step(&mut self);
+
+    /// Add the state to the `Debug` output of a generator named by
+    /// `Scrambler::LEGACY_NAME`.
+    ///
+    /// By default, this adds the state words as `s` and, for indexed engines,
+    /// the index as `p`.
+    fn debug_fields(&self, f: &mut fmt::DebugStruct<'_, '_>) {
+        f.field("s", &self.words());
+        if Self::INDEXED {
+            f.field("p", &self.index());
+        }
+    }
+}
+
+/// An engine whose state words can hold any 128 bits.
+///
+/// Distinct 128-bit seeds map to distinct states of such an engine, see
+/// `Scrambled::from_seed_u128`.  Engines with only 64 bits of state or with
+/// state words of fewer than 64 bits, like the 58-bit words of Erlang, do not
+/// implement this.
+pub trait Seed128: Engine {}
+
+/// An engine which can jump ahead in its sequence.
+pub trait Jump: Engine {
+    /// Jump forward, equivalently to a large power of two of steps.
+    ///
+    /// The number of steps is documented by the implementing engine.
+    fn jump(&mut self);
+}
+
+/// An engine which can jump ahead further than `Jump::jump`.
+pub trait LongJump: Jump {
+    /// Jump forward, equivalently to a larger power of two of steps than
+    /// `jump`.
+    fn long_jump(&mut self);
+}
+
+/// Jump forward by applying the jump polynomial `poly` to the engine.
+///
+/// Bit `b` of `poly[i]` is the coefficient of `x^(W::BITS i + b)`.  The state
+/// words are accumulated relative to the index of the engine.
+pub(crate) fn jump_with<E: Engine>(engine: &mut E, poly: &[E::Word]) {
+    let n = E::WORDS;
+    let mut t = vec![0; n];
+    for &j in poly {
+        let j = j.to_u64();
+        for b in 0..E::Word::BITS {
+            if (j & (1 << b)) != 0 {
+                let p = engine.index();
+                let words = engine.words();
+                for (i, t) in t.iter_mut().enumerate() {
+                    *t ^= words[(i + p) % n].to_u64();
+                }
+            }
+            engine.step();
+        }
+    }
+    let p = engine.index();
+    let mut words = vec![E::Word::default(); n];
+    for (i, &t) in t.iter().enumerate() {
+        words[(i + p) % n] = E::Word::from_u64(t);
+    }
+    // The jump is a bijection, so the new state cannot be entirely zero.
+    *engine = E::from_words(&words, p).unwrap();
+}
@@ -0,0 +1,338 @@
+//! The 58-bit generators of Erlang's `rand` module.
+//!
+//! Erlang uses 58-bit words, because they fit into an immediate integer on
+//! 64-bit platforms.  The state words are stored in `u64`, with the upper six
+//! bits always zero.
+
+use rand_core::RngCore;
+
+use super::{Engine, Jump, SplitMix64, StateError};
+use super::engine::jump_with;
+
+/// The mask of the 58 bits of a word.
+const MASK58: u64 = (1 << 58) - 1;
+
+/// 2^-53, converting 53 bits to a float in [0, 1).
+const TWO_POW_MINUS53: f64 = 1. / (1u64 << 53) as f64;
+
+#[inline]
+fn rotl58(x: u64, n: u32) -> u64 {
+    ((x << n) | (x >> (58 - n))) & MASK58
+}
+
+/// Validate 58-bit state words.
+fn check_words(words: &[u64], index: usize) -> Result<(), StateError> {
+    if words.iter().all(|&x| x == 0) {
+        return Err(StateError::AllZero);
+    }
+    if index != 0 {
+        return Err(StateError::IndexOutOfRange(index));
+    }
+    if let Some(i) = words.iter().position(|&x| x > MASK58) {
+        return Err(StateError::WordOutOfRange(i));
+    }
+    Ok(())
+}
+
+/// Convert a 58-bit output to a float in [0, 1) like `rand:uniform/0`.
+#[inline]
+fn uniform(v: u64) -> f64 {
+    (v >> 5) as f64 * TWO_POW_MINUS53
+}
+
+/// Draw an integer in [1, n] like `rand:uniform/1`.
+///
+/// Erlang uses rejection sampling on the remainder, so the generator may be
+/// advanced several times.
+fn uniform_n<F: FnMut() -> u64>(n: u64, mut next: F) -> u64 {
+    assert!((1..=1 << 58).contains(&n), "n must be between 1 and 2^58.");
+    let max_minus_n = (1 << 58) - n;
+    loop {
+        let v = next();
+        if v < n {
+            return v + 1;
+        }
+        let i = v % n;
+        if v - i <= max_minus_n {
+            return i + 1;
+        }
+    }
+}
+
+/// Draw a non-zero 58-bit word from `SplitMix64` seeded with `x`, like
+/// Erlang's `seed58/1`.  Return the word and the new `SplitMix64` state.
+fn seed58(x: u64) -> (u64, u64) {
+    let mut rng = SplitMix64::from_state(x);
+    loop {
+        let z = rng.next_u64() & MASK58;
+        if z != 0 {
+            return (z, rng.state());
+        }
+    }
+}
+
+/// The xoroshiro116 engine with 58-bit words, as used by Erlang's `exrop`.
+///
+/// It has a period of 2^116 - 1.  `jump` advances it by 2^64 steps.
+#[derive(Debug, Clone)]
+pub struct XoroShiro116Engine {
+    s: [u64; 2],
+}
+
+impl XoroShiro116Engine {
+    fn next_state(s0: u64, s1: u64) -> [u64; 2] {
+        let s1 = s1 ^ s0;
+        [rotl58(s0, 24) ^ s1 ^ ((s1 << 2) & MASK58), rotl58(s1, 35)]
+    }
+}
+
+impl Engine for XoroShiro116Engine {
+    type Seed = [u8; 16];
+    type Word = u64;
+    type State = [u64; 2];
+    const WORDS: usize = 2;
+    const INDEXED: bool = false;
+
+    fn new_unseeded() -> XoroShiro116Engine {
+        ErlangExrop::from_seed_tuple(0, 0, 0).engine
+    }
+
+    /// Create an engine from its state words.
+    ///
+    /// This fails if the words are entirely zero or have more than 58 bits.
+    fn from_words(words: &[u64], index: usize) -> Result<XoroShiro116Engine, StateError> {
+        check_words(words, index)?;
+        Ok(XoroShiro116Engine { s: [words[0], words[1]] })
+    }
+
+    fn words(&self) -> &[u64] {
+        &self.s
+    }
+
+    fn index(&self) -> usize {
+        0
+    }
+
+    /// Return the state `[s0, s1]`.
+    fn state(&self) -> [u64; 2] {
+        self.s
+    }
+
+    fn from_state(state: [u64; 2]) -> Result<XoroShiro116Engine, StateError> {
+        XoroShiro116Engine::from_words(&state, 0)
+    }
+
+    #[inline]
+    fn step(&mut self) {
+        self.s = XoroShiro116Engine::next_state(self.s[0], self.s[1]);
+    }
+}
+
+impl Jump for XoroShiro116Engine {
+    /// Jump forward, equivalently to 2^64 steps.
+    fn jump(&mut self) {
+        // The 116-bit constant `16#9863200f83fcd4a11293241fcb12a` of Erlang.
+        const JUMP: [u64; 2] = [0x4a11293241fcb12a, 0x0009863200f83fcd];
+        jump_with(self, &JUMP);
+    }
+}
+
+/// The `exrop` generator of Erlang's `rand` module, which is xoroshiro116+.
+///
+/// This reproduces the streams of `rand:uniform/0`, `rand:uniform/1` and
+/// `rand:jump/1` for the same state.  The state corresponds to the improper
+/// list `[S0|S1]` in the state returned by `rand:export_seed/0`.
+///
+/// Only ranges up to 2^58 are supported by `uniform_n`.
+#[derive(Debug, Clone)]
+pub struct ErlangExrop {
+    engine: XoroShiro116Engine,
+}
+
+impl ErlangExrop {
+    /// Create a generator like `rand:seed(exrop, {A1, A2, A3})`.
+    ///
+    /// Negative Erlang integers correspond to their two's complement.  This
+    /// panics if the seed is mapped to the all zero state, for which Erlang
+    /// would only produce zeros.
+    pub fn from_seed_tuple(a1: u64, a2: u64, a3: u64) -> ErlangExrop {
+        let x1 = a1.wrapping_mul(4294967197).wrapping_add(1) & MASK58;
+        let x2 = a2.wrapping_mul(4294967231).wrapping_add(1) & MASK58;
+        let x3 = a3.wrapping_mul(4294967279).wrapping_add(1) & MASK58;
+        let s1 = XoroShiro116Engine::next_state(x1, x2)[1];
+        let s = XoroShiro116Engine::next_state(x3, s1);
+        ErlangExrop::from_state(s)
+            .expect("ErlangExrop::from_seed_tuple called with a seed mapping to the all zero state.")
+    }
+
+    /// Create a generator from the state `[S0|S1]`.
+    ///
+    /// This fails if the state is entirely zero or if a word has more than 58
+    /// bits.
+    pub fn from_state(state: [u64; 2]) -> Result<ErlangExrop, StateError> {
+        Ok(ErlangExrop { engine: XoroShiro116Engine::from_state(state)? })
+    }
+
+    /// Return the state `[S0, S1]`.
+    pub fn state(&self) -> [u64; 2] {
+        self.engine.state()
+    }
+
+    /// Return the next 58-bit output.
+    #[inline]
+    pub fn next_u58(&mut self) -> u64 {
+        let r = (self.engine.s[0] + self.engine.s[1]) & MASK58;
+        self.engine.step();
+        r
+    }
+
+    /// Return a float in [0, 1) like `rand:uniform/0`.
+    pub fn uniform(&mut self) -> f64 {
+        uniform(self.next_u58())
+    }
+
+    /// Return an integer in [1, n] like `rand:uniform/1`.
+    ///
+    /// This panics if `n` is zero or larger than 2^58.
+    pub fn uniform_n(&mut self, n: u64) -> u64 {
+        uniform_n(n, || self.next_u58())
+    }
+
+    /// Jump forward like `rand:jump/1`, equivalently to 2^64 calls to
+    /// `next_u58()`.
+    pub fn jump(&mut self) {
+        self.engine.jump();
+    }
+}
+
+/// The xorshift116 engine with 58-bit words, as used by Erlang's `exsss` and
+/// `exsp`.
+///
+/// It has a period of 2^116 - 1.  `jump` advances it by 2^64 steps.
+#[derive(Debug, Clone)]
+pub struct XorShift116Engine {
+    s: [u64; 2],
+}
+
+impl Engine for XorShift116Engine {
+    type Seed = [u8; 16];
+    type Word = u64;
+    type State = [u64; 2];
+    const WORDS: usize = 2;
+    const INDEXED: bool = false;
+
+    fn new_unseeded() -> XorShift116Engine {
+        ErlangExsss::from_seed_tuple(0, 0, 0).engine
+    }
+
+    /// Create an engine from its state words.
+    ///
+    /// This fails if the words are entirely zero or have more than 58 bits.
+    fn from_words(words: &[u64], index: usize) -> Result<XorShift116Engine, StateError> {
+        check_words(words, index)?;
+        Ok(XorShift116Engine { s: [words[0], words[1]] })
+    }
+
+    fn words(&self) -> &[u64] {
+        &self.s
+    }
+
+    fn index(&self) -> usize {
+        0
+    }
+
+    /// Return the state `[s0, s1]`.
+    fn state(&self) -> [u64; 2] {
+        self.s
+    }
+
+    fn from_state(state: [u64; 2]) -> Result<XorShift116Engine, StateError> {
+        XorShift116Engine::from_words(&state, 0)
+    }
+
+    #[inline]
+    fn step(&mut self) {
+        let s1 = self.s[0];
+        let s0 = self.s[1];
+        let s1 = s1 ^ ((s1 << 24) & MASK58);
+        self.s = [s0, s1 ^ s0 ^ (s1 >> 11) ^ (s0 >> 41)];
+    }
+}
+
+impl Jump for XorShift116Engine {
+    /// Jump forward, equivalently to 2^64 steps.
+    fn jump(&mut self) {
+        // The 58-bit constants `16#02f8ea6bc32c797` and `16#345d2a0f85f788c`
+        // of Erlang.
+        const JUMP: [u64; 2] = [0x302f8ea6bc32c797, 0x000d174a83e17de2];
+        jump_with(self, &JUMP);
+    }
+}
+
+/// The `exsss` generator of Erlang's `rand` module, which is xorshift116**.
+///
+/// This is the default algorithm of Erlang since OTP 22.  It reproduces the
+/// streams of `rand:uniform/0`, `rand:uniform/1` and `rand:jump/1` for the
+/// same state.  The state corresponds to the improper list `[S0|S1]` in the
+/// state returned by `rand:export_seed/0`.
+///
+/// Only ranges up to 2^58 are supported by `uniform_n`.
+#[derive(Debug, Clone)]
+pub struct ErlangExsss {
+    engine: XorShift116Engine,
+}
+
+impl ErlangExsss {
+    /// Create a generator like `rand:seed(exsss, {A1, A2, A3})`.
+    ///
+    /// Negative Erlang integers correspond to their two's complement.
+    pub fn from_seed_tuple(a1: u64, a2: u64, a3: u64) -> ErlangExsss {
+        let (_, x0) = seed58(a1);
+        let (s0, x1) = seed58(a2 ^ x0);
+        let (s1, _) = seed58(a3 ^ x1);
+        // `seed58` never returns zero.
+        ErlangExsss::from_state([s0, s1]).unwrap()
+    }
+
+    /// Create a generator from the state `[S0|S1]`.
+    ///
+    /// This fails if the state is entirely zero or if a word has more than 58
+    /// bits.
+    pub fn from_state(state: [u64; 2]) -> Result<ErlangExsss, StateError> {
+        Ok(ErlangExsss { engine: XorShift116Engine::from_state(state)? })
+    }
+
+    /// Return the state `[S0, S1]`.
+    pub fn state(&self) -> [u64; 2] {
+        self.engine.state()
+    }
+
+    /// Return the next 58-bit output.
+    #[inline]
+    pub fn next_u58(&mut self) -> u64 {
+        let s = self.engine.s[1];
+        let v = (s + ((s << 2) & MASK58)) & MASK58;
+        let v = rotl58(v, 7);
+        let r = (v + ((v << 3) & MASK58)) & MASK58;
+        self.engine.step();
+        r
+    }
+
+    /// Return a float in [0, 1) like `rand:uniform/0`.
+    pub fn uniform(&mut self) -> f64 {
+        uniform(self.next_u58())
+    }
+
+    /// Return an integer in [1, n] like `rand:uniform/1`.
+    ///
+    /// This panics if `n` is zero or larger than 2^58.
+    pub fn uniform_n(&mut self, n: u64) -> u64 {
+        uniform_n(n, || self.next_u58())
+    }
+
+    /// Jump forward like `rand:jump/1`, equivalently to 2^64 calls to
+    /// `next_u58()`.
+    pub fn jump(&mut self) {
+        self.engine.jump();
+    }
+}
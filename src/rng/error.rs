@@ -0,0 +1,160 @@
+use std::error::Error;
+use std::fmt;
+
+use super::Algorithm;
+
+/// Error returned when a generator is restored from an invalid state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StateError {
+    /// The state is entirely zero, from which the generator would only ever
+    /// produce zeros.
+    AllZero,
+    /// The index into the state array is out of range.
+    IndexOutOfRange(usize),
+    /// The state word with the given index has more bits than the engine
+    /// uses.
+    WordOutOfRange(usize),
+    /// The increment of the generator is even.
+    EvenGamma,
+}
+
+impl fmt::Display for StateError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            StateError::AllZero => write!(f, "state is entirely zero"),
+            StateError::IndexOutOfRange(p) =>
+                write!(f, "state index {} is out of range", p),
+            StateError::WordOutOfRange(i) =>
+                write!(f, "state word {} is out of range", i),
+            StateError::EvenGamma =>
+                write!(f, "the gamma must be odd"),
+        }
+    }
+}
+
+impl Error for StateError {}
+
+/// Error returned when a generator is restored from an invalid checkpoint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckpointError {
+    /// The checkpoint has the wrong length.
+    InvalidLength,
+    /// The checkpoint does not start with the expected magic bytes.
+    BadMagic,
+    /// The checkpoint was written in an unsupported format version.
+    UnsupportedVersion(u8),
+    /// The checkpoint uses an unknown algorithm identifier.
+    UnknownAlgorithm(u8),
+    /// The checkpoint stores a different algorithm than the one requested.
+    AlgorithmMismatch {
+        /// The requested algorithm.
+        expected: Algorithm,
+        /// The algorithm stored in the checkpoint.
+        found: Algorithm,
+    },
+    /// The checksum of the checkpoint does not match its contents.
+    ChecksumMismatch,
+    /// The checkpoint stores an invalid state.
+    InvalidState(StateError),
+}
+
+impl fmt::Display for CheckpointError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            CheckpointError::InvalidLength =>
+                write!(f, "checkpoint has an invalid length"),
+            CheckpointError::BadMagic =>
+                write!(f, "checkpoint has invalid magic bytes"),
+            CheckpointError::UnsupportedVersion(v) =>
+                write!(f, "checkpoint format version {} is not supported", v),
+            CheckpointError::UnknownAlgorithm(tag) =>
+                write!(f, "checkpoint algorithm {} is unknown", tag),
+            CheckpointError::AlgorithmMismatch { expected, found } =>
+                write!(f, "expected checkpoint of {:?}, found {:?}", expected, found),
+            CheckpointError::ChecksumMismatch =>
+                write!(f, "checkpoint checksum does not match"),
+            CheckpointError::InvalidState(e) =>
+                write!(f, "checkpoint has an invalid state: {}", e),
+        }
+    }
+}
+
+impl Error for CheckpointError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match *self {
+            CheckpointError::InvalidState(ref e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<StateError> for CheckpointError {
+    fn from(e: StateError) -> CheckpointError {
+        CheckpointError::InvalidState(e)
+    }
+}
+
+/// Error returned when a generator is parsed from an invalid state string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseStateError {
+    /// The string does not start with the name of the expected algorithm.
+    InvalidAlgorithm,
+    /// The string has the wrong number of fields.
+    InvalidFieldCount,
+    /// The field with the given index is malformed.
+    InvalidField(usize),
+    /// The string describes an invalid state.
+    InvalidState(StateError),
+}
+
+impl fmt::Display for ParseStateError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ParseStateError::InvalidAlgorithm =>
+                write!(f, "state string has an unexpected algorithm name"),
+            ParseStateError::InvalidFieldCount =>
+                write!(f, "state string has the wrong number of fields"),
+            ParseStateError::InvalidField(i) =>
+                write!(f, "field {} of state string is malformed", i),
+            ParseStateError::InvalidState(e) =>
+                write!(f, "state string describes an invalid state: {}", e),
+        }
+    }
+}
+
+impl Error for ParseStateError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match *self {
+            ParseStateError::InvalidState(ref e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<StateError> for ParseStateError {
+    fn from(e: StateError) -> ParseStateError {
+        ParseStateError::InvalidState(e)
+    }
+}
+
+/// Error returned when engine constants are rejected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParamsError {
+    /// A constant is outside of the valid range.
+    OutOfRange,
+    /// The constants do not give the full period of the engine.
+    NotFullPeriod,
+}
+
+impl fmt::Display for ParamsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ParamsError::OutOfRange =>
+                write!(f, "engine constant is out of range"),
+            ParamsError::NotFullPeriod =>
+                write!(f, "engine constants do not give the full period"),
+        }
+    }
+}
+
+impl Error for ParamsError {}
@@ -0,0 +1,227 @@
+//! Conversions of random integers to floats.
+//!
+//! Only the upper bits of an output are used, because the lower bits of some
+//! generators, for instance those with the `+` scrambler, are weak.  A double
+//! has 53 significant bits and a float 24, so there are 2^53 or 2^24 equally
+//! spaced values in the unit interval.
+
+use rand_core::RngCore;
+
+use super::bounded::below_u128;
+
+/// 2^-53, the spacing of the doubles returned by the conversions.
+const F64_UNIT: f64 = 1. / (1u64 << 53) as f64;
+
+/// 2^-24, the spacing of the floats returned by the conversions.
+const F32_UNIT: f32 = 1. / (1u32 << 24) as f32;
+
+/// Convert the upper 53 bits of `x` to a double in [0, 1).
+#[inline]
+pub(crate) fn f64_closed_open(x: u64) -> f64 {
+    (x >> 11) as f64 * F64_UNIT
+}
+
+/// Convert the upper 52 bits of `x` to a double in (0, 1).
+///
+/// The results are the odd multiples of 2^-53, which are symmetric around
+/// 1/2.
+#[inline]
+pub(crate) fn f64_open(x: u64) -> f64 {
+    ((x >> 11) | 1) as f64 * F64_UNIT
+}
+
+/// Convert the upper 53 bits of `x` to a double in [0, 1].
+///
+/// The results are the multiples of 1 / (2^53 - 1), rounded to the nearest
+/// double, so 1 is reached exactly.
+#[inline]
+pub(crate) fn f64_closed(x: u64) -> f64 {
+    (x >> 11) as f64 / ((1u64 << 53) - 1) as f64
+}
+
+/// Convert the upper 53 bits of `x` to a double in (0, 1].
+#[inline]
+pub(crate) fn f64_open_closed(x: u64) -> f64 {
+    ((x >> 11) + 1) as f64 * F64_UNIT
+}
+
+/// Convert the upper 24 bits of `x` to a float in [0, 1).
+#[inline]
+pub(crate) fn f32_closed_open(x: u32) -> f32 {
+    (x >> 8) as f32 * F32_UNIT
+}
+
+/// Draw a uniform real number in [0, 2^top) rounded down to a double, for
+/// `-1022 <= top <= 1024`.
+///
+/// The exponent is drawn from the leading zeros of the outputs, so that the
+/// interval [2^(top-1-z), 2^(top-z)) is chosen with probability 2^-(z+1), and
+/// the mantissa is drawn uniformly from the upper 52 bits of another output,
+/// as proposed by Allen B. Downey.  Below 2^-1022, the doubles are equally
+/// spaced and the mantissa alone is uniform.
+pub(crate) fn f64_full_precision_below<R: RngCore + ?Sized>(rng: &mut R, top: i32) -> f64 {
+    debug_assert!((-1022..=1024).contains(&top));
+    // More leading zeros would give an exponent below -1022.
+    let max_zeros = top + 1021;
+    let mut zeros = 0;
+    loop {
+        let x = rng.next_u64();
+        zeros += x.leading_zeros() as i32;
+        if x != 0 || zeros > max_zeros {
+            break;
+        }
+    }
+    let m = rng.next_u64() >> 12;
+    if zeros > max_zeros {
+        return f64::from_bits(m);
+    }
+    let exponent = top - 1 - zeros;
+    f64::from_bits((((exponent + 1023) as u64) << 52) | m)
+}
+
+/// Draw a uniform real number in [lo, hi) rounded down to a double.
+///
+/// A range on one side of zero is sampled as a range of magnitudes, which
+/// are rounded up for negative numbers.  A range containing zero is split
+/// there: a side is chosen with probability proportional to its length by
+/// sampling the symmetric range [-m, m), where `m` is the larger length, and
+/// rejecting results outside of [lo, hi), which happens at most half of the
+/// time.
+///
+/// This panics unless `lo < hi` and both are finite.
+pub(crate) fn f64_uniform<R: RngCore + ?Sized>(rng: &mut R, lo: f64, hi: f64) -> f64 {
+    assert!(lo < hi && lo.is_finite() && hi.is_finite(),
+        "uniform_f64 called with an invalid range.");
+    // Adding +0 turns -0 into +0.
+    if lo >= 0. {
+        return magnitude_down(rng, lo + 0., hi);
+    }
+    if hi <= 0. {
+        return -magnitude_up(rng, -hi + 0., -lo);
+    }
+    let m = if -lo > hi { -lo } else { hi };
+    loop {
+        if rng.next_u64() >> 63 == 0 {
+            let x = magnitude_down(rng, 0., m);
+            if x < hi {
+                return x;
+            }
+        } else {
+            let x = magnitude_up(rng, 0., m);
+            if x <= -lo {
+                return -x;
+            }
+        }
+    }
+}
+
+/// Draw a uniform real number in [a, b) rounded down to a double, for
+/// `0 <= a < b` and finite `b`.
+fn magnitude_down<R: RngCore + ?Sized>(rng: &mut R, a: f64, b: f64) -> f64 {
+    let grid = match Grid::new(a, b) {
+        Some(grid) => grid,
+        None => loop {
+            let x = f64_full_precision_below(rng, top_exponent(b));
+            if x >= a && x < b {
+                return x;
+            }
+        },
+    };
+    let mut g = grid.m_a + below_u128(rng, grid.m_b - grid.m_a);
+    let len = 128 - g.leading_zeros();
+    if len > 53 {
+        g = (g >> (len - 53)) << (len - 53);
+    }
+    grid.scale(g)
+}
+
+/// Draw a uniform real number in (a, b] rounded up to a double, for
+/// `0 <= a < b` and finite `b`.
+fn magnitude_up<R: RngCore + ?Sized>(rng: &mut R, a: f64, b: f64) -> f64 {
+    let grid = match Grid::new(a, b) {
+        Some(grid) => grid,
+        None => loop {
+            // Rounding up gives the next double above the rounded down value,
+            // except for exact values, which have probability zero.
+            let x = f64_full_precision_below(rng, top_exponent(b));
+            let x = f64::from_bits(x.to_bits() + 1);
+            if x > a && x <= b {
+                return x;
+            }
+        },
+    };
+    // The real number is in (g - 1, g] times the spacing.
+    let mut g = grid.m_a + 1 + below_u128(rng, grid.m_b - grid.m_a);
+    let len = 128 - g.leading_zeros();
+    if len > 53 {
+        let mask = (1 << (len - 53)) - 1;
+        if g & mask != 0 {
+            // This may carry to a power of two, which is still exact.
+            g = (g | mask) + 1;
+        }
+    }
+    grid.scale(g)
+}
+
+/// Return the smallest `top` with `x <= 2^top`, for a finite `x > 0`.
+fn top_exponent(x: f64) -> i32 {
+    let (exponent, mantissa) = decompose(x);
+    if mantissa == 1 << 52 { exponent } else { exponent + 1 }
+}
+
+/// The multiples of the spacing `u` of the doubles near `a`.
+///
+/// All doubles in [a, b] are multiples of `u`, so drawing a uniform multiple
+/// of `u` and rounding it gives the same distribution as rounding a uniform
+/// real number.  The multiples have less than 117 bits.
+struct Grid {
+    m_a: u128,
+    m_b: u128,
+    u_exponent: i32,
+}
+
+impl Grid {
+    /// Return the grid for [a, b], or `None` if `a` is negligible compared
+    /// to `b`.  In that case, sampling [0, 2^top) with `b <= 2^top` and
+    /// rejecting values outside of the range succeeds in about half of the
+    /// attempts, and conditioned on the result, the underlying real number
+    /// is uniform in the range.
+    fn new(a: f64, b: f64) -> Option<Grid> {
+        let (a_exponent, a_mantissa) = decompose(a);
+        let (b_exponent, b_mantissa) = decompose(b);
+        if top_exponent(b) - a_exponent > 64 {
+            return None;
+        }
+        Some(Grid {
+            m_a: u128::from(a_mantissa),
+            m_b: u128::from(b_mantissa) << (b_exponent - a_exponent),
+            u_exponent: a_exponent - 52,
+        })
+    }
+
+    /// Return `g * u` for a `g` with at most 53 significant bits.
+    ///
+    /// Scaling by a power of two is exact, because the result is
+    /// representable.
+    fn scale(&self, g: u128) -> f64 {
+        if self.u_exponent >= -1022 {
+            g as f64 * f64::from_bits(((self.u_exponent + 1023) as u64) << 52)
+        } else {
+            g as f64 * f64::from_bits(((self.u_exponent + 1074 + 1023) as u64) << 52)
+                * f64::from_bits(1)
+        }
+    }
+}
+
+/// Return `(e, m)` with `x = m * 2^(e - 52)`, where `m < 2^53` and `e` is
+/// at least -1022, for a finite `x >= 0`.
+fn decompose(x: f64) -> (i32, u64) {
+    let bits = x.to_bits();
+    let biased = (bits >> 52) as i32;
+    let mantissa = bits & ((1 << 52) - 1);
+    if biased == 0 {
+        (-1022, mantissa)
+    } else {
+        (biased - 1023, mantissa | (1 << 52))
+    }
+}
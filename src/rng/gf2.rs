@@ -0,0 +1,159 @@
+//! Polynomial arithmetic over GF(2) for the 128-bit xoroshiro engines.
+//!
+//! Polynomials of degree less than 128 are represented as `u128`, where bit `k`
+//! is the coefficient of `x^k`.  A monic polynomial of degree 128 is
+//! represented by its lower coefficients, the coefficient of `x^128` is
+//! implicit.
+//!
+//! Everything is `const`, so that the parameters of `XoroShiro128ParamsCustom`
+//! can be validated and its jump polynomials computed at compile time.
+
+/// The prime factors of 2^128 - 1.
+const FACTORS: [u128; 11] = [
+    3, 5, 17, 257, 641, 65537, 274177, 6700417, 67280421310721,
+    59649589127497217, 5704689200685129054721,
+];
+
+/// Whether the parameters are in the range accepted by the engine.
+pub(crate) const fn xoroshiro128_in_range(a: u32, b: u32, c: u32) -> bool {
+    a > 0 && a < 64 && b > 0 && b < 64 && c > 0 && c < 64
+}
+
+/// Advance the xoroshiro128 state `s` by one step.
+const fn xoroshiro128_step(s: [u64; 2], a: u32, b: u32, c: u32) -> [u64; 2] {
+    let s0 = s[0];
+    let s1 = s[1] ^ s0;
+    [s0.rotate_left(a) ^ s1 ^ (s1 << b), s1.rotate_left(c)]
+}
+
+/// Compute the characteristic polynomial of the xoroshiro128 engine.
+///
+/// This uses the Berlekamp-Massey algorithm on 256 bits of the lowest bit of
+/// `s0`, which yields the minimal polynomial of that sequence.  It is equal to
+/// the characteristic polynomial if it has degree 128, otherwise `None` is
+/// returned.  In that case, the characteristic polynomial is reducible and the
+/// engine does not have a full period.
+pub(crate) const fn xoroshiro128_char_poly(a: u32, b: u32, c: u32) -> Option<u128> {
+    let mut bits = [0u64; 4];
+    let mut s = [1, 0];
+    let mut i = 0;
+    while i < 256 {
+        bits[i / 64] |= (s[0] & 1) << (i % 64);
+        s = xoroshiro128_step(s, a, b, c);
+        i += 1;
+    }
+
+    // Bit `i - 1` of the connection polynomials is the coefficient of `x^i`,
+    // the constant coefficient 1 is implicit.  Their degree cannot exceed 128,
+    // because the state has 128 bits.
+    let mut conn: u128 = 0;
+    let mut prev: u128 = 0;
+    let mut len = 0;
+    let mut m = 1;
+    let mut n = 0;
+    while n < 256 {
+        let mut d = (bits[n / 64] >> (n % 64)) & 1;
+        let mut i = 1;
+        while i <= len {
+            let s = (bits[(n - i) / 64] >> ((n - i) % 64)) & 1;
+            d ^= ((conn >> (i - 1)) & 1) as u64 & s;
+            i += 1;
+        }
+        if d == 0 {
+            m += 1;
+        } else {
+            // conn += x^m (1 + prev)
+            let shifted = (1u128 << (m - 1)) ^ if m < 128 { prev << m } else { 0 };
+            if 2 * len <= n {
+                let old = conn;
+                conn ^= shifted;
+                len = n + 1 - len;
+                prev = old;
+                m = 1;
+            } else {
+                conn ^= shifted;
+                m += 1;
+            }
+        }
+        n += 1;
+    }
+    if len != 128 {
+        return None;
+    }
+
+    // p(x) = x^128 C(1/x), so the coefficient of x^k is the coefficient of
+    // x^(128 - k) in the connection polynomial.
+    Some(conn.reverse_bits())
+}
+
+/// Multiply `a` and `b` modulo the monic polynomial of degree 128 `p`.
+const fn mul_mod(a: u128, b: u128, p: u128) -> u128 {
+    let mut r = 0;
+    let mut i = 128;
+    while i > 0 {
+        i -= 1;
+        let carry = r >> 127;
+        r <<= 1;
+        if carry != 0 {
+            r ^= p;
+        }
+        if (b >> i) & 1 != 0 {
+            r ^= a;
+        }
+    }
+    r
+}
+
+/// Compute `x^e` modulo `p`.
+const fn pow_x_mod(e: u128, p: u128) -> u128 {
+    let mut r = 1;
+    let mut i = 128;
+    while i > 0 {
+        i -= 1;
+        r = mul_mod(r, r, p);
+        if (e >> i) & 1 != 0 {
+            // Multiplying by x is a shift.
+            let carry = r >> 127;
+            r <<= 1;
+            if carry != 0 {
+                r ^= p;
+            }
+        }
+    }
+    r
+}
+
+/// Compute `x^(2^k)` modulo `p`.
+const fn pow_x_pow2_mod(k: u32, p: u128) -> u128 {
+    let mut r = 2;
+    let mut i = 0;
+    while i < k {
+        r = mul_mod(r, r, p);
+        i += 1;
+    }
+    r
+}
+
+/// Whether the monic polynomial of degree 128 `p` is primitive.
+///
+/// This is the case if and only if the order of `x` modulo `p` is 2^128 - 1.
+pub(crate) const fn is_primitive(p: u128) -> bool {
+    if pow_x_pow2_mod(128, p) != 2 {
+        return false;
+    }
+    let mut i = 0;
+    while i < FACTORS.len() {
+        if pow_x_mod(u128::MAX / FACTORS[i], p) == 1 {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+/// Compute the jump polynomial for 2^k steps of the engine with the
+/// characteristic polynomial `p`, as little-endian words.
+pub(crate) const fn jump_poly(k: u32, p: u128) -> [u64; 2] {
+    let j = pow_x_pow2_mod(k, p);
+    [j as u64, (j >> 64) as u64]
+}
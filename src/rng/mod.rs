@@ -1,7 +1,48 @@
+mod bit_buffer;
+mod bounded;
+mod byte_stream;
+mod checkpoint;
+mod engine;
+mod erlang;
+mod error;
+mod float;
+mod gf2;
+mod permutation;
+mod scrambled;
+mod scrambler;
+mod seed;
 mod splitmix64;
+mod state_str;
+mod xoroshiro64;
 mod xoroshiro128;
+mod xoroshiro1024;
+mod xorshift128;
 mod xorshift1024;
+mod xoshiro128;
+mod xoshiro256;
+mod xoshiro512;
 
-pub use self::splitmix64::SplitMix64;
-pub use self::xoroshiro128::XoroShiro128;
-pub use self::xorshift1024::{XorShift1024, XorShift1024Seed};
+pub use self::bit_buffer::BitBuffer;
+pub use self::byte_stream::{Advance, ByteStream};
+pub use self::checkpoint::{Algorithm, KnownAlgorithm};
+pub use self::engine::{Engine, Jump, LongJump, Seed128, Word};
+pub use self::erlang::{XoroShiro116Engine, ErlangExrop, XorShift116Engine, ErlangExsss};
+pub use self::error::{CheckpointError, ParamsError, ParseStateError, StateError};
+pub use self::permutation::{RandomPermutation, RandomPermutationIter};
+pub use self::scrambled::Scrambled;
+pub use self::scrambler::{Scrambler, Plus, Star, Product, StarStar, PlusPlus, StarPhi};
+pub use self::seed::{XorShift1024Seed, Xoshiro512Seed};
+pub use self::splitmix64::{AtomicSplitMix64, SplitMix64, SplittableRandom};
+pub use self::xoroshiro64::{XoroShiro64Engine, XoroShiro64Star, XoroShiro64StarStar};
+pub use self::xoroshiro128::{XoroShiro128Engine, XoroShiro128Params, XoroShiro128Params2016,
+                             XoroShiro128Params2018, XoroShiro128ParamsPlusPlus,
+                             XoroShiro128ParamsCustom, check_xoroshiro128_params};
+pub use self::xoroshiro128::{XoroShiro128, XoroShiro128Plus2018, XoroShiro128Star,
+                             XoroShiro128StarStar, XoroShiro128PlusPlus};
+pub use self::xoroshiro1024::{XoroShiro1024Engine, XoroShiro1024StarStar, XoroShiro1024PlusPlus};
+pub use self::xorshift128::{XorShift128Engine, XorShift128Plus, V8MathRandom};
+pub use self::xorshift1024::{XorShift1024Engine, XorShift1024};
+pub use self::xoshiro128::{Xoshiro128Engine, Xoshiro128StarStar, Xoshiro128PlusPlus};
+pub use self::xoshiro256::{Xoshiro256Engine, Xoshiro256Plus, Xoshiro256PlusPlus,
+                           Xoshiro256StarStar, LuaRandom, DotNetRandom};
+pub use self::xoshiro512::{Xoshiro512Engine, Xoshiro512StarStar, Xoshiro512PlusPlus};
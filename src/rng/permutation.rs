@@ -0,0 +1,163 @@
+use hash::mix64;
+use super::SplitMix64;
+
+/// The number of Feistel rounds.
+const ROUNDS: usize = 6;
+
+/// A random permutation of `0..n` that is computed on demand.
+///
+/// Indices are mapped by a balanced Feistel network on the smallest even
+/// number of bits covering `0..n`, with round keys derived from the seed by
+/// `SplitMix64` and `mix64` as round function.  Results outside of `0..n` are
+/// mapped again until they fall inside (cycle walking), which takes less than
+/// four rounds on average.  This uses constant memory, so it can visit huge
+/// ranges in random order without storing a shuffled array.
+///
+/// The permutation is not cryptographically secure.
+///
+/// ```
+/// # extern crate xoroshiro;
+/// # fn main() {
+/// use xoroshiro::rng::RandomPermutation;
+///
+/// let perm = RandomPermutation::new(1_000_000_000_000, 42);
+/// let j = perm.get(123);
+/// assert!(j < 1_000_000_000_000);
+/// assert_eq!(perm.inverse(j), 123);
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct RandomPermutation {
+    n: u64,
+    half_bits: u32,
+    keys: [u64; ROUNDS],
+}
+
+impl RandomPermutation {
+    /// Create the permutation of `0..n` for the given seed.
+    pub fn new(n: u64, seed: u64) -> RandomPermutation {
+        let bits = 64 - n.saturating_sub(1).leading_zeros();
+        let mut keys = [0; ROUNDS];
+        for (i, k) in keys.iter_mut().enumerate() {
+            *k = SplitMix64::at(seed, i as u64);
+        }
+        RandomPermutation {
+            n,
+            half_bits: ::std::cmp::max(bits.div_ceil(2), 1),
+            keys,
+        }
+    }
+
+    /// Return the number of elements of the permutation.
+    pub fn len(&self) -> u64 {
+        self.n
+    }
+
+    /// Return whether the permutation is empty.
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    /// Return the element at position `i`.
+    ///
+    /// This panics if `i` is not less than `len()`.
+    pub fn get(&self, i: u64) -> u64 {
+        assert!(i < self.n, "RandomPermutation::get called with an index out of range.");
+        let mut x = self.encrypt(i);
+        while x >= self.n {
+            x = self.encrypt(x);
+        }
+        x
+    }
+
+    /// Return the position of the element `j`, such that
+    /// `get(inverse(j)) == j`.
+    ///
+    /// This panics if `j` is not less than `len()`.
+    pub fn inverse(&self, j: u64) -> u64 {
+        assert!(j < self.n, "RandomPermutation::inverse called with an element out of range.");
+        let mut x = self.decrypt(j);
+        while x >= self.n {
+            x = self.decrypt(x);
+        }
+        x
+    }
+
+    /// Iterate over the elements in the order of the permutation.
+    pub fn iter(&self) -> RandomPermutationIter<'_> {
+        RandomPermutationIter {
+            perm: self,
+            i: 0,
+        }
+    }
+
+    #[inline]
+    fn mask(&self) -> u64 {
+        (1 << self.half_bits) - 1
+    }
+
+    #[inline]
+    fn round(&self, r: usize, x: u64) -> u64 {
+        mix64(x ^ self.keys[r]) & self.mask()
+    }
+
+    fn encrypt(&self, x: u64) -> u64 {
+        let mut l = x >> self.half_bits;
+        let mut r = x & self.mask();
+        for i in 0..ROUNDS {
+            let t = l ^ self.round(i, r);
+            l = r;
+            r = t;
+        }
+        (l << self.half_bits) | r
+    }
+
+    fn decrypt(&self, x: u64) -> u64 {
+        let mut l = x >> self.half_bits;
+        let mut r = x & self.mask();
+        for i in (0..ROUNDS).rev() {
+            let t = r ^ self.round(i, l);
+            r = l;
+            l = t;
+        }
+        (l << self.half_bits) | r
+    }
+}
+
+impl<'a> IntoIterator for &'a RandomPermutation {
+    type Item = u64;
+    type IntoIter = RandomPermutationIter<'a>;
+
+    fn into_iter(self) -> RandomPermutationIter<'a> {
+        self.iter()
+    }
+}
+
+/// An iterator over the elements of a `RandomPermutation`.
+#[derive(Debug, Clone)]
+pub struct RandomPermutationIter<'a> {
+    perm: &'a RandomPermutation,
+    i: u64,
+}
+
+impl<'a> Iterator for RandomPermutationIter<'a> {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        if self.i >= self.perm.n {
+            return None;
+        }
+        let j = self.perm.get(self.i);
+        self.i += 1;
+        Some(j)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.perm.n - self.i;
+        if remaining > usize::MAX as u64 {
+            (usize::MAX, None)
+        } else {
+            (remaining as usize, Some(remaining as usize))
+        }
+    }
+}
@@ -0,0 +1,485 @@
+use std::fmt;
+use std::marker::PhantomData;
+use std::str::FromStr;
+
+use rand_core;
+use rand_core::{RngCore, SeedableRng};
+use byteorder::{LittleEndian, ByteOrder};
+#[cfg(feature = "serde")]
+use serde::{Serialize, Serializer, Deserialize, Deserializer};
+
+use super::{CheckpointError, Engine, Jump, KnownAlgorithm, LongJump, ParseStateError,
+            Scrambler, Seed128, SplitMix64, StateError, Word};
+use super::{checkpoint, state_str};
+use super::bounded::{below_u128, below_u32, below_u64, range_i64};
+use super::engine::{pack_words, unpack_words};
+use super::float::{f32_closed_open, f64_closed, f64_closed_open, f64_full_precision_below, f64_open,
+                   f64_open_closed, f64_uniform};
+use super::splitmix64::expand_seed;
+
+/// A random number generator combining an engine with a scrambler.
+///
+/// The engine `E` defines the linear state transition, the scrambler `S`
+/// computes the output from the state.  The generators of this crate are type
+/// aliases of `Scrambled`, for example `XoroShiro128` combines the xoroshiro128
+/// engine with the `+` scrambler.  Seeding, jumping, checkpoints and state
+/// strings are implemented once for all combinations.
+pub struct Scrambled<E, S> {
+    engine: E,
+    scrambler: PhantomData<S>,
+}
+
+impl<E: Clone, S> Clone for Scrambled<E, S> {
+    fn clone(&self) -> Scrambled<E, S> {
+        Scrambled::from_engine(self.engine.clone())
+    }
+}
+
+impl<E: Engine + fmt::Debug, S: Scrambler<E>> fmt::Debug for Scrambled<E, S> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match S::LEGACY_NAME {
+            Some(name) => {
+                let mut d = f.debug_struct(name);
+                self.engine.debug_fields(&mut d);
+                d.finish()
+            }
+            None => {
+                f.debug_struct("Scrambled")
+                    .field("engine", &self.engine)
+                    .finish()
+            }
+        }
+    }
+}
+
+impl<E, S> Scrambled<E, S> {
+    /// Create a new generator from an engine.
+    pub fn from_engine(engine: E) -> Scrambled<E, S> {
+        Scrambled {
+            engine,
+            scrambler: PhantomData,
+        }
+    }
+
+    /// Return the engine of the generator.
+    pub fn engine(&self) -> &E {
+        &self.engine
+    }
+}
+
+impl<E: Engine, S: Scrambler<E>> Scrambled<E, S> {
+    /// Creates a new instance which is not seeded.
+    ///
+    /// The initial values of this RNG are constants, so all generators created
+    /// by this function will yield the same stream of random numbers. It is
+    /// highly recommended that this is created through `SeedableRng` instead of
+    /// this function.
+    pub fn new_unseeded() -> Scrambled<E, S> {
+        Scrambled::from_engine(E::new_unseeded())
+    }
+
+    /// Create a new generator with its state filled by `SplitMix64` seeded
+    /// with `seed`.
+    pub fn from_seed_u64(seed: u64) -> Scrambled<E, S> {
+        let mut rng = SplitMix64::from_seed_u64(seed);
+        Scrambled::from_rng(&mut rng).unwrap()
+    }
+
+    /// Create a new generator from 64-bit seed words, up to the size of the
+    /// state.
+    ///
+    /// The words are expanded using `SplitMix64`, such that distinct seeds of
+    /// the same length give distinct states.  Missing words are treated as
+    /// zero.
+    ///
+    /// This fails if the seed is mapped to an invalid state, for instance the
+    /// all zero state.  This will panic if the seed is larger than the state.
+    pub fn from_seed_u64s(seed: &[u64]) -> Result<Scrambled<E, S>, StateError> {
+        assert!(seed.len() <= E::u64_words(),
+            "Scrambled::from_seed_u64s called with a seed larger than the state.");
+        let mut packed = vec![0; E::u64_words()];
+        expand_seed(seed, &mut packed);
+        let mut words = vec![E::Word::default(); E::WORDS];
+        unpack_words(&packed, &mut words);
+        E::from_words(&words, 0).map(Scrambled::from_engine)
+    }
+
+    /// Return the internal state of the generator.
+    pub fn state(&self) -> E::State {
+        self.engine.state()
+    }
+
+    /// Create a new generator from a state previously returned by `state`.
+    ///
+    /// This fails if the state is invalid, for instance if it is entirely
+    /// zero.
+    pub fn from_state(state: E::State) -> Result<Scrambled<E, S>, StateError> {
+        E::from_state(state).map(Scrambled::from_engine)
+    }
+
+    /// Return a double in [0, 1), computed from the upper 53 bits of
+    /// `next_u64()`.
+    ///
+    /// The result is a multiple of 2^-53.  Unlike generic conversions, this
+    /// never uses the lower bits, which are weak for some scramblers.
+    ///
+    /// ```
+    /// # extern crate xoroshiro;
+    /// # fn main() {
+    /// use xoroshiro::rng::XoroShiro128;
+    ///
+    /// let mut rng = XoroShiro128::from_seed_u64(0);
+    /// let x = rng.next_f64();
+    /// assert!(0. <= x && x < 1.);
+    /// # }
+    /// ```
+    #[inline]
+    pub fn next_f64(&mut self) -> f64 {
+        f64_closed_open(S::next_u64(&mut self.engine))
+    }
+
+    /// Return a float in [0, 1), computed from the upper 24 bits of
+    /// `next_u32()`.
+    ///
+    /// The result is a multiple of 2^-24.
+    #[inline]
+    pub fn next_f32(&mut self) -> f32 {
+        f32_closed_open(S::next_u32(&mut self.engine))
+    }
+
+    /// Return a double in (0, 1), computed from the upper 52 bits of
+    /// `next_u64()`.
+    ///
+    /// The result is an odd multiple of 2^-53, so it is never 0 or 1.
+    #[inline]
+    pub fn next_f64_open(&mut self) -> f64 {
+        f64_open(S::next_u64(&mut self.engine))
+    }
+
+    /// Return a double in [0, 1], computed from the upper 53 bits of
+    /// `next_u64()`.
+    ///
+    /// The result is the nearest double to a multiple of 1 / (2^53 - 1), so
+    /// both 0 and 1 are possible.
+    #[inline]
+    pub fn next_f64_closed(&mut self) -> f64 {
+        f64_closed(S::next_u64(&mut self.engine))
+    }
+
+    /// Return a double in (0, 1], computed from the upper 53 bits of
+    /// `next_u64()`.
+    ///
+    /// The result is a positive multiple of 2^-53, for instance for taking
+    /// its logarithm.
+    #[inline]
+    pub fn next_f64_open_closed(&mut self) -> f64 {
+        f64_open_closed(S::next_u64(&mut self.engine))
+    }
+
+    /// Return a double in [0, 1) where every representable value is possible.
+    ///
+    /// The result is a uniform real number in [0, 1) rounded down to a double,
+    /// so each double `x` has a probability equal to the distance from `x` to
+    /// the next double.  Unlike `next_f64`, values below 2^-53 can occur and
+    /// small values have random low bits.  The exponent is drawn from the
+    /// leading zeros of the outputs and the mantissa from the upper 52 bits of
+    /// another output, so this usually consumes two outputs.
+    ///
+    /// ```
+    /// # extern crate xoroshiro;
+    /// # fn main() {
+    /// use xoroshiro::rng::XoroShiro128;
+    ///
+    /// let mut rng = XoroShiro128::from_seed_u64(0);
+    /// let x = rng.next_f64_full_precision();
+    /// assert!(0. <= x && x < 1.);
+    /// # }
+    /// ```
+    pub fn next_f64_full_precision(&mut self) -> f64 {
+        f64_full_precision_below(self, 0)
+    }
+
+    /// Return a double in [lo, hi) where every representable value is
+    /// possible.
+    ///
+    /// The result is a uniform real number in [lo, hi) rounded down to a
+    /// double, so each double `x` in the range has a probability proportional
+    /// to the distance from `x` to the next double.  Unlike `lo + (hi - lo) *
+    /// next_f64()`, this never rounds outside the range and has no bias.
+    ///
+    /// The range may contain negative numbers.  This panics unless `lo < hi`
+    /// and both are finite.
+    pub fn uniform_f64(&mut self, lo: f64, hi: f64) -> f64 {
+        f64_uniform(self, lo, hi)
+    }
+
+    /// Return a uniform integer in [0, n), computed from the upper bits of
+    /// `next_u32()`.
+    ///
+    /// This uses Lemire's multiply-shift method, which rejects only a tiny
+    /// fraction of the outputs to avoid any bias and rarely needs a division.
+    /// The result only depends on the outputs, not on the platform.  This
+    /// panics if `n` is 0.
+    ///
+    /// ```
+    /// # extern crate xoroshiro;
+    /// # fn main() {
+    /// use xoroshiro::rng::XoroShiro128;
+    ///
+    /// let mut rng = XoroShiro128::from_seed_u64(0);
+    /// let die = rng.gen_below_u32(6) + 1;
+    /// assert!(1 <= die && die <= 6);
+    /// # }
+    /// ```
+    #[inline]
+    pub fn gen_below_u32(&mut self, n: u32) -> u32 {
+        below_u32(self, n)
+    }
+
+    /// Return a uniform integer in [0, n), computed from the upper bits of
+    /// `next_u64()`, like `gen_below_u32`.
+    ///
+    /// This panics if `n` is 0.
+    #[inline]
+    pub fn gen_below_u64(&mut self, n: u64) -> u64 {
+        below_u64(self, n)
+    }
+
+    /// Return a uniform integer in [0, n), computed from the upper bits of
+    /// two calls to `next_u64()`, like `gen_below_u32`.
+    ///
+    /// The first output gives the upper 64 bits of the random word.  This
+    /// panics if `n` is 0.
+    pub fn gen_below_u128(&mut self, n: u128) -> u128 {
+        below_u128(self, n)
+    }
+
+    /// Return a uniform integer in [lo, hi), like `gen_below_u64`.
+    ///
+    /// The result is `lo + gen_below_u64(hi - lo)`, so every range of
+    /// `i64` is supported.  This panics unless `lo < hi`.
+    #[inline]
+    pub fn gen_range_i64(&mut self, lo: i64, hi: i64) -> i64 {
+        range_i64(self, lo, hi)
+    }
+}
+
+impl<E: Jump, S: Scrambler<E>> Scrambled<E, S> {
+    /// Jump forward, equivalently to a large power of two of calls to
+    /// `next_u64()`, as documented by the engine.
+    ///
+    /// This can be used to generate non-overlapping subsequences for parallel
+    /// computations.
+    ///
+    /// ```
+    /// # extern crate rand;
+    /// # extern crate xoroshiro;
+    /// # fn main() {
+    /// use rand::SeedableRng;
+    /// use xoroshiro::rng::XoroShiro128;
+    ///
+    /// let rng1 = XoroShiro128::from_seed_u64(0);
+    /// let mut rng2 = rng1.clone();
+    /// rng2.jump();
+    /// let mut rng3 = rng2.clone();
+    /// rng3.jump();
+    /// # }
+    /// ```
+    pub fn jump(&mut self) {
+        self.engine.jump();
+    }
+}
+
+impl<E: LongJump, S: Scrambler<E>> Scrambled<E, S> {
+    /// Jump forward further than `jump`, as documented by the engine.
+    ///
+    /// This can be used to generate starting points, from each of which
+    /// `jump()` will generate non-overlapping subsequences for parallel
+    /// distributed computations.
+    pub fn long_jump(&mut self) {
+        self.engine.long_jump();
+    }
+}
+
+impl<E: Engine, S: Scrambler<E>> Scrambled<E, S> where Scrambled<E, S>: KnownAlgorithm {
+    /// Serialize the state of the generator as a checkpoint.
+    ///
+    /// State words with less than 64 bits are packed into little-endian 64-bit
+    /// words.  For indexed engines, the index is stored as an additional state
+    /// word after the state words.  See `from_checkpoint_bytes` for restoring
+    /// it.
+    pub fn to_checkpoint_bytes(&self) -> Vec<u8> {
+        let mut words = pack_words(self.engine.words());
+        if E::INDEXED {
+            words.push(self.engine.index() as u64);
+        }
+        checkpoint::encode(Self::ALGORITHM, &words)
+    }
+
+    /// Restore a generator from a checkpoint created by `to_checkpoint_bytes`.
+    ///
+    /// This fails if the checkpoint is corrupted, stores a different algorithm
+    /// or an invalid state.
+    pub fn from_checkpoint_bytes(bytes: &[u8]) -> Result<Scrambled<E, S>, CheckpointError> {
+        let n = E::u64_words();
+        let mut packed = vec![0; n + E::INDEXED as usize];
+        checkpoint::decode(bytes, Self::ALGORITHM, &mut packed)?;
+        let index = if E::INDEXED {
+            ::std::cmp::min(packed[n], usize::MAX as u64) as usize
+        } else {
+            0
+        };
+        let mut words = vec![E::Word::default(); E::WORDS];
+        unpack_words(&packed[..n], &mut words);
+        Ok(Scrambled::from_engine(E::from_words(&words, index)?))
+    }
+}
+
+/// Fill `dest` with the little-endian bytes of successive `next_u32` calls.
+///
+/// The remaining bytes of the last output are discarded.
+#[inline]
+pub(crate) fn fill_bytes_via_u32<R: RngCore + ?Sized>(rng: &mut R, dest: &mut [u8]) {
+    for chunk in dest.chunks_mut(4) {
+        if chunk.len() == 4 {
+            LittleEndian::write_u32(chunk, rng.next_u32());
+        } else {
+            debug_assert!(chunk.len() < 4);
+            let r = rng.next_u32();
+            for (i, v) in chunk.iter_mut().enumerate() {
+                *v = (r >> (8*i)) as u8;
+            }
+        }
+    }
+}
+
+/// Fill `dest` with the little-endian bytes of successive `next_u64` calls.
+///
+/// The remaining bytes of the last output are discarded.
+#[inline]
+pub(crate) fn fill_bytes_via_u64<R: RngCore + ?Sized>(rng: &mut R, dest: &mut [u8]) {
+    for chunk in dest.chunks_mut(8) {
+        if chunk.len() == 8 {
+            LittleEndian::write_u64(chunk, rng.next_u64());
+        } else {
+            debug_assert!(chunk.len() < 8);
+            let r = rng.next_u64();
+            for (i, v) in chunk.iter_mut().enumerate() {
+                *v = (r >> (8*i)) as u8;
+            }
+        }
+    }
+}
+
+impl<E: Engine, S: Scrambler<E>> RngCore for Scrambled<E, S> {
+    #[inline]
+    fn next_u32(&mut self) -> u32 {
+        S::next_u32(&mut self.engine)
+    }
+
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        S::next_u64(&mut self.engine)
+    }
+
+    #[inline]
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        // Engines with 32-bit words produce 32 bits at a time.
+        if E::Word::BITS == 32 {
+            fill_bytes_via_u32(self, dest)
+        } else {
+            fill_bytes_via_u64(self, dest)
+        }
+    }
+
+    #[inline]
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+impl<E: Seed128, S: Scrambler<E>> Scrambled<E, S> {
+    /// Create a new generator from a 128-bit seed.
+    ///
+    /// This is equivalent to calling `from_seed_u64s` with the low and the high
+    /// 64 bits of `seed`, so distinct seeds give distinct generators.  For
+    /// engines with 128 bits of state, this fails for the single seed
+    /// `0x61c8864680b583eb61c8864680b583eb`, which is mapped to the all zero
+    /// state.
+    pub fn from_seed_u128(seed: u128) -> Result<Scrambled<E, S>, StateError> {
+        Scrambled::from_seed_u64s(&[seed as u64, (seed >> 64) as u64])
+    }
+}
+
+impl<E: Engine, S: Scrambler<E>> SeedableRng for Scrambled<E, S> {
+    type Seed = E::Seed;
+
+    /// Create a new generator.  This will panic if `seed` is entirely 0.
+    fn from_seed(mut seed: E::Seed) -> Scrambled<E, S> {
+        let mut words = vec![E::Word::default(); E::WORDS];
+        E::Word::read_le(seed.as_mut(), &mut words);
+        match E::from_words(&words, 0) {
+            Ok(engine) => Scrambled::from_engine(engine),
+            Err(_) => panic!("{}::from_seed called with an all zero seed.",
+                             S::LEGACY_NAME.unwrap_or("Scrambled")),
+        }
+    }
+}
+
+/// Formats the state as the name of the algorithm followed by the state words
+/// as 16 hexadecimal digits each (8 for 32-bit words) and, for indexed engines,
+/// the decimal index, all separated by `:`.  For example, the state of
+/// `XoroShiro128` is formatted like
+/// `xoroshiro128+:193a6754a8a7d469:97830e05113ba7bb`.  It can be parsed back
+/// with `FromStr`.
+impl<E: Engine, S: Scrambler<E>> fmt::Display for Scrambled<E, S>
+    where Scrambled<E, S>: KnownAlgorithm
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        state_str::write_words(f, Self::ALGORITHM.name(), self.engine.words())?;
+        if E::INDEXED {
+            write!(f, ":{}", self.engine.index())?;
+        }
+        Ok(())
+    }
+}
+
+impl<E: Engine, S: Scrambler<E>> FromStr for Scrambled<E, S>
+    where Scrambled<E, S>: KnownAlgorithm
+{
+    type Err = ParseStateError;
+
+    fn from_str(s: &str) -> Result<Scrambled<E, S>, ParseStateError> {
+        let fields = state_str::split_fields(
+            s, Self::ALGORITHM.name(), E::WORDS + E::INDEXED as usize)?;
+        let mut words = vec![E::Word::default(); E::WORDS];
+        for (i, w) in words.iter_mut().enumerate() {
+            *w = state_str::parse_word(fields[i], i)?;
+        }
+        let index = if E::INDEXED {
+            state_str::parse_index(fields[E::WORDS], E::WORDS)?
+        } else {
+            0
+        };
+        Ok(Scrambled::from_engine(E::from_words(&words, index)?))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<E: Serialize, S> Serialize for Scrambled<E, S> {
+    fn serialize<Ser>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error>
+        where Ser: Serializer
+    {
+        self.engine.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, E: Deserialize<'de>, S> Deserialize<'de> for Scrambled<E, S> {
+    fn deserialize<D>(deserializer: D) -> Result<Scrambled<E, S>, D::Error>
+        where D: Deserializer<'de>
+    {
+        E::deserialize(deserializer).map(Scrambled::from_engine)
+    }
+}
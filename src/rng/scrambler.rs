@@ -0,0 +1,67 @@
+/// The output function of a generator.
+///
+/// A scrambler computes the output of a generator from the state of an
+/// engine and advances the engine.  Whether the output is computed before or
+/// after advancing is up to the scrambler, as defined by the respective
+/// reference implementation.
+pub trait Scrambler<E> {
+    /// The type name of `Scrambled<E, Self>`, for generators which existed
+    /// before `Scrambled`.
+    ///
+    /// Those generators keep their original `Debug` output, the name followed
+    /// by the state fields of the engine, and their original panic messages.
+    /// Other generators are shown as `Scrambled` with their engine.
+    const LEGACY_NAME: Option<&'static str> = None;
+
+    /// Compute the next output and advance the engine.
+    fn next_u64(engine: &mut E) -> u64;
+
+    /// Compute the next 32-bit output and advance the engine.
+    ///
+    /// By default, this uses the upper bits of `next_u64`, which have better
+    /// statistical properties for all scramblers of this crate.
+    #[inline]
+    fn next_u32(engine: &mut E) -> u32 {
+        (Self::next_u64(engine) >> 32) as u32
+    }
+}
+
+/// The `+` scrambler, adding two state words.
+#[derive(Debug, Clone, Copy)]
+pub struct Plus;
+
+/// The `*` scrambler, multiplying a state word by a constant.
+#[derive(Debug, Clone, Copy)]
+pub struct Star;
+
+/// The scrambler of the early xoroshiro128* variant, multiplying the two
+/// state words.
+///
+/// It was superseded by `Star` and is only provided for comparisons with
+/// that variant.
+#[derive(Debug, Clone, Copy)]
+pub struct Product;
+
+/// The `**` scrambler, multiplying a state word by 5, rotating it and
+/// multiplying it by 9.
+#[derive(Debug, Clone, Copy)]
+pub struct StarStar;
+
+/// The `++` scrambler, rotating the sum of two state words and adding one of
+/// them.
+#[derive(Debug, Clone, Copy)]
+pub struct PlusPlus;
+
+/// The `*φ` scrambler of xorshift1024*φ, multiplying a state word by a
+/// constant derived from the golden ratio.
+#[derive(Debug, Clone, Copy)]
+pub struct StarPhi;
+
+/// Compose a 64-bit output from two 32-bit outputs of the scrambler `S`, the
+/// first one providing the lower bits.
+#[inline]
+pub(crate) fn next_u64_via_u32<S: Scrambler<E>, E>(engine: &mut E) -> u64 {
+    let x = u64::from(S::next_u32(engine));
+    let y = u64::from(S::next_u32(engine));
+    (y << 32) | x
+}
@@ -0,0 +1,194 @@
+//! Seed types for generators with more than 256 bits of state.
+//!
+//! Arrays of more than 32 bytes do not implement `Default`, which is required
+//! by `SeedableRng::Seed`, so these wrap the seed bytes.
+
+use std::fmt;
+use std::str::FromStr;
+
+#[cfg(feature = "serde")]
+use serde::{Serialize, Serializer, Deserialize, Deserializer, de};
+
+use super::ParseStateError;
+use super::state_str;
+
+/// Seed for the generators with 1024 bits of state, `XorShift1024`,
+/// `XoroShiro1024StarStar` and `XoroShiro1024PlusPlus`.
+#[derive(Clone)]
+pub struct XorShift1024Seed(pub [u8; 16 * 8]);
+
+impl ::std::convert::From<[u8; 16 * 8]> for XorShift1024Seed {
+    fn from(seed: [u8; 16 * 8]) -> XorShift1024Seed {
+        XorShift1024Seed(seed)
+    }
+}
+
+impl ::std::convert::AsMut<[u8]> for XorShift1024Seed {
+    fn as_mut(&mut self) -> &mut [u8] {
+        &mut self.0
+    }
+}
+
+impl ::std::default::Default for XorShift1024Seed {
+    fn default() -> XorShift1024Seed {
+        XorShift1024Seed([0; 16 * 8])
+    }
+}
+
+/// Formats the seed as `xorshift1024seed:` followed by the 128 seed bytes as
+/// 256 hexadecimal digits.  It can be parsed back with `FromStr`.
+impl fmt::Display for XorShift1024Seed {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_seed(f, "xorshift1024seed", &self.0)
+    }
+}
+
+impl FromStr for XorShift1024Seed {
+    type Err = ParseStateError;
+
+    fn from_str(s: &str) -> Result<XorShift1024Seed, ParseStateError> {
+        let mut seed = XorShift1024Seed::default();
+        parse_seed(s, "xorshift1024seed", &mut seed.0)?;
+        Ok(seed)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for XorShift1024Seed {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer
+    {
+        serializer.serialize_bytes(&self.0)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for XorShift1024Seed {
+    fn deserialize<D>(deserializer: D) -> Result<XorShift1024Seed, D::Error>
+        where D: Deserializer<'de>
+    {
+        let mut seed = XorShift1024Seed::default();
+        deserializer.deserialize_bytes(SeedVisitor(&mut seed.0))?;
+        Ok(seed)
+    }
+}
+
+/// Seed for the generators with 512 bits of state, `Xoshiro512StarStar` and
+/// `Xoshiro512PlusPlus`.
+#[derive(Clone)]
+pub struct Xoshiro512Seed(pub [u8; 8 * 8]);
+
+impl ::std::convert::From<[u8; 8 * 8]> for Xoshiro512Seed {
+    fn from(seed: [u8; 8 * 8]) -> Xoshiro512Seed {
+        Xoshiro512Seed(seed)
+    }
+}
+
+impl ::std::convert::AsMut<[u8]> for Xoshiro512Seed {
+    fn as_mut(&mut self) -> &mut [u8] {
+        &mut self.0
+    }
+}
+
+impl ::std::default::Default for Xoshiro512Seed {
+    fn default() -> Xoshiro512Seed {
+        Xoshiro512Seed([0; 8 * 8])
+    }
+}
+
+/// Formats the seed as `xoshiro512seed:` followed by the 64 seed bytes as 128
+/// hexadecimal digits.  It can be parsed back with `FromStr`.
+impl fmt::Display for Xoshiro512Seed {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_seed(f, "xoshiro512seed", &self.0)
+    }
+}
+
+impl FromStr for Xoshiro512Seed {
+    type Err = ParseStateError;
+
+    fn from_str(s: &str) -> Result<Xoshiro512Seed, ParseStateError> {
+        let mut seed = Xoshiro512Seed::default();
+        parse_seed(s, "xoshiro512seed", &mut seed.0)?;
+        Ok(seed)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for Xoshiro512Seed {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer
+    {
+        serializer.serialize_bytes(&self.0)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Xoshiro512Seed {
+    fn deserialize<D>(deserializer: D) -> Result<Xoshiro512Seed, D::Error>
+        where D: Deserializer<'de>
+    {
+        let mut seed = Xoshiro512Seed::default();
+        deserializer.deserialize_bytes(SeedVisitor(&mut seed.0))?;
+        Ok(seed)
+    }
+}
+
+/// Write the name followed by the seed bytes as hexadecimal digits.
+fn write_seed(f: &mut fmt::Formatter, name: &str, seed: &[u8]) -> fmt::Result {
+    f.write_str(name)?;
+    f.write_str(":")?;
+    for b in seed {
+        write!(f, "{:02x}", b)?;
+    }
+    Ok(())
+}
+
+/// Parse a seed written by `write_seed` into `seed`.
+fn parse_seed(s: &str, name: &str, seed: &mut [u8]) -> Result<(), ParseStateError> {
+    let fields = state_str::split_fields(s, name, 1)?;
+    if !state_str::is_hex(fields[0], 2 * seed.len()) {
+        return Err(ParseStateError::InvalidField(0));
+    }
+    for (i, b) in seed.iter_mut().enumerate() {
+        *b = u8::from_str_radix(&fields[0][2*i..2*i + 2], 16)
+            .map_err(|_| ParseStateError::InvalidField(0))?;
+    }
+    Ok(())
+}
+
+/// Deserialize exactly `self.0.len()` seed bytes into `self.0`.
+#[cfg(feature = "serde")]
+struct SeedVisitor<'a>(&'a mut [u8]);
+
+#[cfg(feature = "serde")]
+impl<'a, 'de> de::Visitor<'de> for SeedVisitor<'a> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} bytes", self.0.len())
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<(), E>
+        where E: de::Error
+    {
+        if v.len() != self.0.len() {
+            return Err(de::Error::invalid_length(v.len(), &self));
+        }
+        self.0.copy_from_slice(v);
+        Ok(())
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<(), A::Error>
+        where A: de::SeqAccess<'de>
+    {
+        for i in 0..self.0.len() {
+            self.0[i] = seq.next_element()?
+                .ok_or_else(|| de::Error::invalid_length(i, &self))?;
+        }
+        if seq.next_element::<u8>()?.is_some() {
+            return Err(de::Error::invalid_length(self.0.len() + 1, &self));
+        }
+        Ok(())
+    }
+}
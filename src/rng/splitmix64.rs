@@ -1,7 +1,23 @@
+use std::fmt;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, Ordering};
+
 use rand_core;
 use rand_core::{RngCore, SeedableRng};
 use byteorder::{LittleEndian, ByteOrder};
 
+use super::{Advance, Algorithm, CheckpointError, ParseStateError, StateError};
+use super::{checkpoint, state_str};
+use super::bounded::{below_u128, below_u32, below_u64, range_i64};
+use super::float::{f32_closed_open, f64_closed, f64_closed_open, f64_full_precision_below, f64_open,
+                   f64_open_closed, f64_uniform};
+use super::scrambled::fill_bytes_via_u64;
+use hash::{mix64, mod_inverse, Finalizer, Murmur3, Stafford04};
+#[cfg(feature = "serde")]
+use serde::{Serialize, Serializer, Deserialize, Deserializer, de};
+#[cfg(feature = "serde")]
+use serde::ser::SerializeStruct;
+
 /// A splitmix random number generator.
 ///
 /// The splitmix algorithm is not suitable for cryptographic purposes, but is
@@ -12,10 +28,19 @@ use byteorder::{LittleEndian, ByteOrder};
 /// The algorithm used here is translated from [the `splitmix64.c`
 /// reference source code](http://xorshift.di.unimi.it/splitmix64.c) by
 /// Sebastiano Vigna.
+///
+/// The state is advanced by adding an odd increment, the gamma.  It is
+/// `0x9e3779b97f4a7c15` unless the generator was created by `split`, which
+/// derives a child generator with a different gamma, as described in the
+/// paper "Fast splittable pseudorandom number generators" by Guy L. Steele
+/// Jr., Doug Lea and Christine H. Flood.
 #[allow(missing_copy_implementations)]
 #[derive(Debug, Clone)]
 pub struct SplitMix64 {
     x: u64,
+    gamma: u64,
+    // The state the generator started from, for `position`.
+    origin: u64,
 }
 
 impl SplitMix64 {
@@ -27,9 +52,7 @@ impl SplitMix64 {
     /// this function.
     pub fn new_unseeded() -> SplitMix64 {
         // The state can be seeded with any value.
-        SplitMix64 {
-            x: 0,
-        }
+        SplitMix64::from_state(0)
     }
 
     pub fn from_seed_u64(seed: u64) -> SplitMix64 {
@@ -37,6 +60,304 @@ impl SplitMix64 {
         LittleEndian::write_u64(&mut x, seed);
         SplitMix64::from_seed(x)
     }
+
+    /// Return the internal state of the generator.
+    ///
+    /// This does not include the gamma, see `gamma`.
+    pub fn state(&self) -> u64 {
+        self.x
+    }
+
+    /// Create a new `SplitMix64` from a state previously returned by `state`.
+    ///
+    /// Any value is a valid state for this generator.  The gamma is
+    /// `0x9e3779b97f4a7c15`, see `from_state_gamma` for restoring a generator
+    /// created by `split`.
+    pub fn from_state(state: u64) -> SplitMix64 {
+        SplitMix64 {
+            x: state,
+            gamma: GOLDEN_GAMMA,
+            origin: state,
+        }
+    }
+
+    /// Return the gamma, which is added to the state on each step.
+    pub fn gamma(&self) -> u64 {
+        self.gamma
+    }
+
+    /// Create a new `SplitMix64` from a state and a gamma previously returned
+    /// by `state` and `gamma`.
+    ///
+    /// This fails if the gamma is even.
+    pub fn from_state_gamma(state: u64, gamma: u64) -> Result<SplitMix64, StateError> {
+        if gamma & 1 == 0 {
+            return Err(StateError::EvenGamma);
+        }
+        Ok(SplitMix64 {
+            x: state,
+            gamma,
+            origin: state,
+        })
+    }
+
+    /// Split off a new generator.
+    ///
+    /// The new generator is seeded from the next output of this generator and
+    /// gets a new gamma, so its stream is independent of the stream of this
+    /// generator.  This advances this generator twice.  Recursively splitting
+    /// generators hands each task of a fork/join computation its own
+    /// generator without coordination.
+    ///
+    /// ```
+    /// # extern crate rand;
+    /// # extern crate xoroshiro;
+    /// # fn main() {
+    /// use rand::RngCore;
+    /// use xoroshiro::rng::SplitMix64;
+    ///
+    /// let mut parent = SplitMix64::from_seed_u64(0);
+    /// let mut left = parent.split();
+    /// let mut right = parent.split();
+    /// assert_ne!(left.gamma(), right.gamma());
+    /// assert_ne!(left.next_u64(), right.next_u64());
+    /// # }
+    /// ```
+    pub fn split(&mut self) -> SplitMix64 {
+        let x = self.next_u64();
+        self.x = self.x.wrapping_add(self.gamma);
+        SplitMix64 {
+            x,
+            gamma: mix_gamma(self.x),
+            origin: x,
+        }
+    }
+
+    /// Return output `index` of the generator created by `from_seed_u64(seed)`
+    /// without generating the previous outputs.
+    ///
+    /// The state after `n` steps is `seed + n * gamma`, so every output can
+    /// be computed directly.  This allows to generate the outputs in any order
+    /// or in parallel with identical results.
+    ///
+    /// ```
+    /// # extern crate rand;
+    /// # extern crate xoroshiro;
+    /// # fn main() {
+    /// use rand::RngCore;
+    /// use xoroshiro::rng::SplitMix64;
+    ///
+    /// let mut rng = SplitMix64::from_seed_u64(7);
+    /// rng.next_u64();
+    /// assert_eq!(SplitMix64::at(7, 1), rng.next_u64());
+    /// # }
+    /// ```
+    #[inline]
+    pub fn at(seed: u64, index: u64) -> u64 {
+        mix64(seed.wrapping_add(GOLDEN_GAMMA.wrapping_mul(index.wrapping_add(1))))
+    }
+
+    /// Fill `dest` with the outputs `start`, `start + 1`, ... of the generator
+    /// created by `from_seed_u64(seed)`, like `at`.
+    pub fn fill_range(seed: u64, start: u64, dest: &mut [u64]) {
+        let mut x = seed.wrapping_add(GOLDEN_GAMMA.wrapping_mul(start));
+        for v in dest {
+            x = x.wrapping_add(GOLDEN_GAMMA);
+            *v = mix64(x);
+        }
+    }
+
+    /// Advance the generator by `n` steps, equivalently to `n` calls to
+    /// `next_u64()`.
+    ///
+    /// This takes constant time.  Advancing by `2^64 - n` steps goes back by
+    /// `n` steps.
+    pub fn advance(&mut self, n: u64) {
+        self.x = self.x.wrapping_add(self.gamma.wrapping_mul(n));
+    }
+
+    /// Return the number of steps since the generator was created, modulo
+    /// 2^64.
+    ///
+    /// This counts calls to `next_u64()` and the steps of `advance` and
+    /// `split`.  For a generator restored from a state, checkpoint, string or
+    /// serialization, it counts from the restored state, see `position_from`
+    /// for counting across such a round trip.
+    pub fn position(&self) -> u64 {
+        self.position_from(self.origin)
+    }
+
+    /// Return the number of steps from the state `start` to the current
+    /// state, modulo 2^64.
+    ///
+    /// `start` is a state previously returned by `state` for a generator
+    /// with the same gamma, for instance before the generator was saved and
+    /// restored.
+    ///
+    /// ```
+    /// # extern crate rand;
+    /// # extern crate xoroshiro;
+    /// # fn main() {
+    /// use rand::RngCore;
+    /// use xoroshiro::rng::SplitMix64;
+    ///
+    /// let mut rng = SplitMix64::from_seed_u64(7);
+    /// let start = rng.state();
+    /// rng.next_u64();
+    /// rng.advance(41);
+    /// let restored = SplitMix64::from_state(rng.state());
+    /// assert_eq!(restored.position(), 0);
+    /// assert_eq!(restored.position_from(start), 42);
+    /// # }
+    /// ```
+    pub fn position_from(&self, start: u64) -> u64 {
+        self.x.wrapping_sub(start).wrapping_mul(mod_inverse(self.gamma))
+    }
+
+    /// Return a double in [0, 1) from the upper 53 bits of `next_u64()`.
+    #[inline]
+    pub fn next_f64(&mut self) -> f64 {
+        f64_closed_open(self.next_u64())
+    }
+
+    /// Return a float in [0, 1) from the upper 24 bits of `next_u64()`.
+    #[inline]
+    pub fn next_f32(&mut self) -> f32 {
+        f32_closed_open((self.next_u64() >> 32) as u32)
+    }
+
+    /// Return a double in (0, 1) from the upper 52 bits of `next_u64()`.
+    #[inline]
+    pub fn next_f64_open(&mut self) -> f64 {
+        f64_open(self.next_u64())
+    }
+
+    /// Return a double in [0, 1] from the upper 53 bits of `next_u64()`.
+    #[inline]
+    pub fn next_f64_closed(&mut self) -> f64 {
+        f64_closed(self.next_u64())
+    }
+
+    /// Return a double in (0, 1] from the upper 53 bits of `next_u64()`.
+    #[inline]
+    pub fn next_f64_open_closed(&mut self) -> f64 {
+        f64_open_closed(self.next_u64())
+    }
+
+    /// Return a double in [0, 1) where every representable value is possible,
+    /// like `Scrambled::next_f64_full_precision`.
+    pub fn next_f64_full_precision(&mut self) -> f64 {
+        f64_full_precision_below(self, 0)
+    }
+
+    /// Return a double in [lo, hi) where every representable value is
+    /// possible, like `Scrambled::uniform_f64`.
+    ///
+    /// This panics unless `lo < hi` and both are finite.
+    pub fn uniform_f64(&mut self, lo: f64, hi: f64) -> f64 {
+        f64_uniform(self, lo, hi)
+    }
+
+    /// Return a uniform integer in [0, n) from `next_u32()`, like
+    /// `Scrambled::gen_below_u32`.
+    ///
+    /// This panics if `n` is 0.
+    #[inline]
+    pub fn gen_below_u32(&mut self, n: u32) -> u32 {
+        below_u32(self, n)
+    }
+
+    /// Return a uniform integer in [0, n) from `next_u64()`, like
+    /// `Scrambled::gen_below_u64`.
+    ///
+    /// This panics if `n` is 0.
+    #[inline]
+    pub fn gen_below_u64(&mut self, n: u64) -> u64 {
+        below_u64(self, n)
+    }
+
+    /// Return a uniform integer in [0, n) from two calls to `next_u64()`,
+    /// like `Scrambled::gen_below_u128`.
+    ///
+    /// This panics if `n` is 0.
+    pub fn gen_below_u128(&mut self, n: u128) -> u128 {
+        below_u128(self, n)
+    }
+
+    /// Return a uniform integer in [lo, hi), like `Scrambled::gen_range_i64`.
+    ///
+    /// This panics unless `lo < hi`.
+    #[inline]
+    pub fn gen_range_i64(&mut self, lo: i64, hi: i64) -> i64 {
+        range_i64(self, lo, hi)
+    }
+
+    /// Serialize the state of the generator as a checkpoint.
+    ///
+    /// The gamma is only stored if it differs from `0x9e3779b97f4a7c15`.  See
+    /// `from_checkpoint_bytes` for restoring it.
+    pub fn to_checkpoint_bytes(&self) -> Vec<u8> {
+        if self.gamma == GOLDEN_GAMMA {
+            checkpoint::encode(Algorithm::SplitMix64, &[self.x])
+        } else {
+            checkpoint::encode(Algorithm::SplitMix64, &[self.x, self.gamma])
+        }
+    }
+
+    /// Restore a generator from a checkpoint created by `to_checkpoint_bytes`.
+    ///
+    /// This fails if the checkpoint is corrupted, stores a different
+    /// algorithm or an even gamma.
+    pub fn from_checkpoint_bytes(bytes: &[u8]) -> Result<SplitMix64, CheckpointError> {
+        let mut state = [0; 2];
+        match checkpoint::decode(bytes, Algorithm::SplitMix64, &mut state) {
+            Ok(()) => Ok(SplitMix64::from_state_gamma(state[0], state[1])?),
+            Err(CheckpointError::InvalidLength) => {
+                let mut state = [0; 1];
+                checkpoint::decode(bytes, Algorithm::SplitMix64, &mut state)?;
+                Ok(SplitMix64::from_state(state[0]))
+            }
+            Err(e) => Err(e),
+        }
+    }
+}
+
+/// Fill `state` with an injective expansion of the words in `seed`.
+///
+/// Each state word is the first output of a `SplitMix64` seeded with the
+/// corresponding seed word xored with the previous state word.  Every step is a
+/// bijection, so distinct seeds give distinct states.  Missing seed words are
+/// treated as zero.
+pub(crate) fn expand_seed(seed: &[u64], state: &mut [u64]) {
+    debug_assert!(seed.len() <= state.len());
+    let mut prev = 0;
+    for (i, s) in state.iter_mut().enumerate() {
+        let word = seed.get(i).cloned().unwrap_or(0);
+        prev = SplitMix64::from_seed_u64(word ^ prev).next_u64();
+        *s = prev;
+    }
+}
+
+/// The increment of `SplitMix64`, the odd integer closest to 2^64 / φ.
+pub(crate) const GOLDEN_GAMMA: u64 = 0x9e3779b97f4a7c15;
+
+/// The 32-bit output function of `SplittableRandom`.
+#[inline]
+fn mix32(z: u64) -> u32 {
+    (Stafford04::mix(z) >> 32) as u32
+}
+
+/// Derive an odd gamma from `z`.
+///
+/// Gammas with fewer than 24 bit transitions are considered weak, their
+/// alternate bits are flipped.
+pub(crate) fn mix_gamma(z: u64) -> u64 {
+    let z = Murmur3::mix(z) | 1;
+    if (z ^ (z >> 1)).count_ones() < 24 {
+        z ^ 0xaaaaaaaaaaaaaaaa
+    } else {
+        z
+    }
 }
 
 impl RngCore for SplitMix64 {
@@ -47,28 +368,13 @@ impl RngCore for SplitMix64 {
 
     #[inline]
     fn next_u64(&mut self) -> u64 {
-        self.x = self.x.wrapping_add(0x9e3779b97f4a7c15);
-        let mut z = self.x;
-        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
-        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
-        return z ^ (z >> 31);
+        self.x = self.x.wrapping_add(self.gamma);
+        mix64(self.x)
     }
 
     #[inline]
     fn fill_bytes(&mut self, dest: &mut [u8]) {
-        for mut chunk in dest.chunks_mut(8) {
-            if chunk.len() == 8 {
-                LittleEndian::write_u64(&mut chunk, self.next_u64());
-            } else {
-                debug_assert!(chunk.len() < 8);
-                let r = self.next_u64();
-                let mut i = 0;
-                for v in chunk.iter_mut() {
-                    *v = (r >> 8*i) as u8;
-                    i += 1;
-                }
-            }
-        }
+        fill_bytes_via_u64(self, dest)
     }
 
     #[inline]
@@ -78,13 +384,443 @@ impl RngCore for SplitMix64 {
     }
 }
 
+impl Advance for SplitMix64 {
+    #[inline]
+    fn advance(&mut self, n: u64) {
+        SplitMix64::advance(self, n)
+    }
+}
+
 impl SeedableRng for SplitMix64 {
     type Seed = [u8; 8];
 
     /// Create a new `SplitMix64`.
     fn from_seed(seed: [u8; 8]) -> SplitMix64 {
-        SplitMix64 {
-            x: LittleEndian::read_u64(&seed),
+        SplitMix64::from_state(LittleEndian::read_u64(&seed))
+    }
+}
+
+/// Formats the state as `splitmix64:` followed by the state as 16 hexadecimal
+/// digits.  If the gamma differs from `0x9e3779b97f4a7c15`, it follows as
+/// another field of 16 hexadecimal digits.  It can be parsed back with
+/// `FromStr`.
+impl fmt::Display for SplitMix64 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.gamma == GOLDEN_GAMMA {
+            state_str::write_words(f, Algorithm::SplitMix64.name(), &[self.x])
+        } else {
+            state_str::write_words(f, Algorithm::SplitMix64.name(), &[self.x, self.gamma])
+        }
+    }
+}
+
+impl FromStr for SplitMix64 {
+    type Err = ParseStateError;
+
+    fn from_str(s: &str) -> Result<SplitMix64, ParseStateError> {
+        let mut state = [0; 2];
+        match state_str::parse_words(s, Algorithm::SplitMix64.name(), &mut state) {
+            Ok(()) => Ok(SplitMix64::from_state_gamma(state[0], state[1])?),
+            Err(ParseStateError::InvalidFieldCount) => {
+                let mut state = [0; 1];
+                state_str::parse_words(s, Algorithm::SplitMix64.name(), &mut state)?;
+                Ok(SplitMix64::from_state(state[0]))
+            }
+            Err(e) => Err(e),
+        }
+    }
+}
+
+/// A `SplitMix64` generator that can be shared between threads.
+///
+/// The state is advanced with a single atomic addition, so the generator is
+/// used through `&self` without locking.  `RngCore` is implemented for
+/// `&AtomicSplitMix64`.  Every output is produced exactly once: the outputs of
+/// all threads together are the same as the outputs of a `SplitMix64` with
+/// the same state, only their order depends on the scheduling.
+///
+/// ```
+/// # extern crate rand;
+/// # extern crate xoroshiro;
+/// # fn main() {
+/// use std::sync::Arc;
+/// use std::thread;
+/// use rand::RngCore;
+/// use xoroshiro::rng::AtomicSplitMix64;
+///
+/// let rng = Arc::new(AtomicSplitMix64::from_seed_u64(0));
+/// let handles: Vec<_> = (0..4).map(|_| {
+///     let rng = rng.clone();
+///     thread::spawn(move || (&*rng).next_u64())
+/// }).collect();
+/// for h in handles {
+///     h.join().unwrap();
+/// }
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct AtomicSplitMix64 {
+    x: AtomicU64,
+    gamma: u64,
+}
+
+impl AtomicSplitMix64 {
+    /// Create a new generator with the same state as
+    /// `SplitMix64::from_seed_u64(seed)`.
+    pub fn from_seed_u64(seed: u64) -> AtomicSplitMix64 {
+        AtomicSplitMix64::from(SplitMix64::from_seed_u64(seed))
+    }
+
+    /// Return the current state of the generator, see `SplitMix64::state`.
+    pub fn state(&self) -> u64 {
+        self.x.load(Ordering::Relaxed)
+    }
+
+    /// Return the gamma, which is added to the state on each step.
+    pub fn gamma(&self) -> u64 {
+        self.gamma
+    }
+
+    /// Convert into a sequential generator with the same state and gamma.
+    pub fn into_inner(self) -> SplitMix64 {
+        // The gamma was odd when it was created.
+        SplitMix64::from_state_gamma(self.x.into_inner(), self.gamma).unwrap()
+    }
+
+    /// Return a double in [0, 1), like `SplitMix64::next_f64`.
+    #[inline]
+    pub fn next_f64(&self) -> f64 {
+        let mut rng = self;
+        f64_closed_open(rng.next_u64())
+    }
+
+    /// Return a float in [0, 1), like `SplitMix64::next_f32`.
+    #[inline]
+    pub fn next_f32(&self) -> f32 {
+        let mut rng = self;
+        f32_closed_open((rng.next_u64() >> 32) as u32)
+    }
+
+    /// Return a double in (0, 1), like `SplitMix64::next_f64_open`.
+    #[inline]
+    pub fn next_f64_open(&self) -> f64 {
+        let mut rng = self;
+        f64_open(rng.next_u64())
+    }
+
+    /// Return a double in [0, 1], like `SplitMix64::next_f64_closed`.
+    #[inline]
+    pub fn next_f64_closed(&self) -> f64 {
+        let mut rng = self;
+        f64_closed(rng.next_u64())
+    }
+
+    /// Return a double in (0, 1], like `SplitMix64::next_f64_open_closed`.
+    #[inline]
+    pub fn next_f64_open_closed(&self) -> f64 {
+        let mut rng = self;
+        f64_open_closed(rng.next_u64())
+    }
+
+    /// Reserve `n` consecutive steps and return the state before them.
+    #[inline]
+    fn reserve(&self, n: u64) -> u64 {
+        self.x.fetch_add(self.gamma.wrapping_mul(n), Ordering::Relaxed)
+    }
+}
+
+impl From<SplitMix64> for AtomicSplitMix64 {
+    fn from(rng: SplitMix64) -> AtomicSplitMix64 {
+        AtomicSplitMix64 {
+            x: AtomicU64::new(rng.x),
+            gamma: rng.gamma,
+        }
+    }
+}
+
+impl RngCore for &AtomicSplitMix64 {
+    #[inline]
+    fn next_u32(&mut self) -> u32 {
+        self.next_u64() as u32
+    }
+
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        mix64(self.reserve(1).wrapping_add(self.gamma))
+    }
+
+    /// Fill `dest` like `SplitMix64::fill_bytes`.
+    ///
+    /// The required outputs are reserved at once, so the bytes are the same
+    /// as those of a sequential generator at the same state.
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        let x = self.reserve((dest.len() as u64).div_ceil(8));
+        let mut rng = SplitMix64 {
+            x,
+            gamma: self.gamma,
+            origin: x,
+        };
+        fill_bytes_via_u64(&mut rng, dest);
+    }
+
+    #[inline]
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+/// A generator reproducing `java.util.SplittableRandom`.
+///
+/// Unlike `SplitMix64`, each instance has its own odd increment, the gamma,
+/// and `split` derives an independent child with a new gamma.  The outputs
+/// and the conversions to ranges and floats are the same as in Java 8 to 17.
+///
+/// ```
+/// # extern crate xoroshiro;
+/// # fn main() {
+/// use xoroshiro::rng::SplittableRandom;
+///
+/// // Same as `new SplittableRandom(42).nextLong()` in Java.
+/// let mut rng = SplittableRandom::from_seed(42);
+/// assert_eq!(rng.next_i64(), -4767286540954276203);
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct SplittableRandom {
+    seed: u64,
+    gamma: u64,
+}
+
+impl SplittableRandom {
+    /// Create a generator like `new SplittableRandom(seed)`.
+    pub fn from_seed(seed: i64) -> SplittableRandom {
+        SplittableRandom {
+            seed: seed as u64,
+            gamma: GOLDEN_GAMMA,
+        }
+    }
+
+    /// Create a generator like `new SplittableRandom()`, where `gen` is the
+    /// value of the static `defaultGen` of Java.
+    ///
+    /// Like in Java, `gen` is advanced, so that successive calls create
+    /// different generators.
+    pub fn from_default_gen(gen: &mut u64) -> SplittableRandom {
+        let s = *gen;
+        *gen = s.wrapping_add(GOLDEN_GAMMA.wrapping_mul(2));
+        SplittableRandom {
+            seed: mix64(s),
+            gamma: mix_gamma(s.wrapping_add(GOLDEN_GAMMA)),
+        }
+    }
+
+    /// Return the state `[seed, gamma]`.
+    pub fn state(&self) -> [u64; 2] {
+        [self.seed, self.gamma]
+    }
+
+    /// Create a generator from a state previously returned by `state`.
+    ///
+    /// This fails if the gamma is even.
+    pub fn from_state(state: [u64; 2]) -> Result<SplittableRandom, StateError> {
+        if state[1] & 1 == 0 {
+            return Err(StateError::EvenGamma);
+        }
+        Ok(SplittableRandom {
+            seed: state[0],
+            gamma: state[1],
+        })
+    }
+
+    #[inline]
+    fn next_seed(&mut self) -> u64 {
+        self.seed = self.seed.wrapping_add(self.gamma);
+        self.seed
+    }
+
+    /// Split off a new generator, like `split()`.
+    ///
+    /// The new generator has a different gamma, so its stream is independent
+    /// of the stream of this generator.
+    pub fn split(&mut self) -> SplittableRandom {
+        let seed = self.next_i64() as u64;
+        let gamma = mix_gamma(self.next_seed());
+        SplittableRandom { seed, gamma }
+    }
+
+    /// Return a random integer, like `nextInt()`.
+    pub fn next_i32(&mut self) -> i32 {
+        mix32(self.next_seed()) as i32
+    }
+
+    /// Return a random integer, like `nextLong()`.
+    pub fn next_i64(&mut self) -> i64 {
+        mix64(self.next_seed()) as i64
+    }
+
+    /// Return an integer in [0, bound), like `nextInt(bound)`.
+    ///
+    /// This panics if `bound` is not positive.
+    pub fn next_i32_below(&mut self, bound: i32) -> i32 {
+        assert!(bound > 0, "bound must be positive");
+        let mut r = self.next_i32();
+        let m = bound - 1;
+        if bound & m == 0 {
+            return r & m;
+        }
+        let mut u = (r as u32 >> 1) as i32;
+        loop {
+            r = u % bound;
+            if u.wrapping_add(m).wrapping_sub(r) >= 0 {
+                return r;
+            }
+            u = (self.next_i32() as u32 >> 1) as i32;
+        }
+    }
+
+    /// Return an integer in [origin, bound), like `nextInt(origin, bound)`.
+    ///
+    /// This panics if `origin` is not less than `bound`.
+    pub fn next_i32_range(&mut self, origin: i32, bound: i32) -> i32 {
+        assert!(origin < bound, "bound must be greater than origin");
+        let mut r = self.next_i32();
+        let n = bound.wrapping_sub(origin);
+        let m = n.wrapping_sub(1);
+        if n & m == 0 {
+            return (r & m).wrapping_add(origin);
+        }
+        if n > 0 {
+            let mut u = (r as u32 >> 1) as i32;
+            loop {
+                r = u % n;
+                if u.wrapping_add(m).wrapping_sub(r) >= 0 {
+                    return r + origin;
+                }
+                u = (self.next_i32() as u32 >> 1) as i32;
+            }
         }
+        // The range is larger than `i32::MAX`.
+        while r < origin || r >= bound {
+            r = self.next_i32();
+        }
+        r
+    }
+
+    /// Return an integer in [0, bound), like `nextLong(bound)`.
+    ///
+    /// This panics if `bound` is not positive.
+    pub fn next_i64_below(&mut self, bound: i64) -> i64 {
+        assert!(bound > 0, "bound must be positive");
+        let mut r = self.next_i64();
+        let m = bound - 1;
+        if bound & m == 0 {
+            return r & m;
+        }
+        let mut u = (r as u64 >> 1) as i64;
+        loop {
+            r = u % bound;
+            if u.wrapping_add(m).wrapping_sub(r) >= 0 {
+                return r;
+            }
+            u = (self.next_i64() as u64 >> 1) as i64;
+        }
+    }
+
+    /// Return an integer in [origin, bound), like `nextLong(origin, bound)`.
+    ///
+    /// This panics if `origin` is not less than `bound`.
+    pub fn next_i64_range(&mut self, origin: i64, bound: i64) -> i64 {
+        assert!(origin < bound, "bound must be greater than origin");
+        let mut r = self.next_i64();
+        let n = bound.wrapping_sub(origin);
+        let m = n.wrapping_sub(1);
+        if n & m == 0 {
+            return (r & m).wrapping_add(origin);
+        }
+        if n > 0 {
+            let mut u = (r as u64 >> 1) as i64;
+            loop {
+                r = u % n;
+                if u.wrapping_add(m).wrapping_sub(r) >= 0 {
+                    return r + origin;
+                }
+                u = (self.next_i64() as u64 >> 1) as i64;
+            }
+        }
+        // The range is larger than `i64::MAX`.
+        while r < origin || r >= bound {
+            r = self.next_i64();
+        }
+        r
+    }
+
+    /// Return a double in [0, 1), like `nextDouble()`.
+    pub fn next_f64(&mut self) -> f64 {
+        f64_closed_open(self.next_i64() as u64)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for SplitMix64 {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer
+    {
+        let mut state = serializer.serialize_struct("SplitMix64", 2)?;
+        state.serialize_field("x", &self.x)?;
+        state.serialize_field("gamma", &self.gamma)?;
+        state.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for SplitMix64 {
+    fn deserialize<D>(deserializer: D) -> Result<SplitMix64, D::Error>
+        where D: Deserializer<'de>
+    {
+        fn golden_gamma() -> u64 {
+            GOLDEN_GAMMA
+        }
+
+        #[derive(Deserialize)]
+        #[serde(rename = "SplitMix64")]
+        struct State {
+            x: u64,
+            // Missing in the format without splitting.
+            #[serde(default = "golden_gamma")]
+            gamma: u64,
+        }
+
+        let state = State::deserialize(deserializer)?;
+        SplitMix64::from_state_gamma(state.x, state.gamma).map_err(de::Error::custom)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for SplittableRandom {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer
+    {
+        let mut state = serializer.serialize_struct("SplittableRandom", 2)?;
+        state.serialize_field("seed", &self.seed)?;
+        state.serialize_field("gamma", &self.gamma)?;
+        state.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for SplittableRandom {
+    fn deserialize<D>(deserializer: D) -> Result<SplittableRandom, D::Error>
+        where D: Deserializer<'de>
+    {
+        #[derive(Deserialize)]
+        #[serde(rename = "SplittableRandom")]
+        struct State {
+            seed: u64,
+            gamma: u64,
+        }
+
+        let state = State::deserialize(deserializer)?;
+        SplittableRandom::from_state([state.seed, state.gamma]).map_err(de::Error::custom)
     }
 }
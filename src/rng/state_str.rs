@@ -0,0 +1,70 @@
+//! Helpers for the canonical textual representation of generator states.
+//!
+//! A state string consists of the algorithm name followed by the fields of the
+//! state, all separated by `:`.  State words are written as exactly 16
+//! lowercase hexadecimal digits, or 8 for 32-bit words.
+
+use std::fmt;
+
+use super::{ParseStateError, Word};
+
+/// Write the algorithm name followed by the state words.
+pub(crate) fn write_words<W: Word>(f: &mut fmt::Formatter, name: &str, words: &[W])
+    -> fmt::Result
+{
+    f.write_str(name)?;
+    for w in words {
+        write!(f, ":{:01$x}", w, W::BITS as usize / 4)?;
+    }
+    Ok(())
+}
+
+/// Check the algorithm name and split the remaining string into exactly `n`
+/// fields.
+pub(crate) fn split_fields<'a>(s: &'a str, name: &str, n: usize)
+    -> Result<Vec<&'a str>, ParseStateError>
+{
+    let mut parts = s.split(':');
+    if parts.next() != Some(name) {
+        return Err(ParseStateError::InvalidAlgorithm);
+    }
+    let fields: Vec<&str> = parts.collect();
+    if fields.len() != n {
+        return Err(ParseStateError::InvalidFieldCount);
+    }
+    Ok(fields)
+}
+
+/// Return whether `field` consists of `len` lowercase hexadecimal digits.
+pub(crate) fn is_hex(field: &str, len: usize) -> bool {
+    field.len() == len &&
+        field.bytes().all(|b| b.is_ascii_digit() || (b'a'..=b'f').contains(&b))
+}
+
+/// Parse the state word in field `i`.
+pub(crate) fn parse_word<W: Word>(field: &str, i: usize) -> Result<W, ParseStateError> {
+    if !is_hex(field, W::BITS as usize / 4) {
+        return Err(ParseStateError::InvalidField(i));
+    }
+    u64::from_str_radix(field, 16).map(W::from_u64).map_err(|_| ParseStateError::InvalidField(i))
+}
+
+/// Parse the decimal index in field `i`, rejecting signs and leading zeros.
+pub(crate) fn parse_index(field: &str, i: usize) -> Result<usize, ParseStateError> {
+    let valid = !field.is_empty() &&
+        field.bytes().all(|b| b.is_ascii_digit()) &&
+        (field == "0" || !field.starts_with('0'));
+    if !valid {
+        return Err(ParseStateError::InvalidField(i));
+    }
+    field.parse().map_err(|_| ParseStateError::InvalidField(i))
+}
+
+/// Parse all fields as state words.
+pub(crate) fn parse_words(s: &str, name: &str, words: &mut [u64]) -> Result<(), ParseStateError> {
+    let fields = split_fields(s, name, words.len())?;
+    for (i, (w, field)) in words.iter_mut().zip(fields).enumerate() {
+        *w = parse_word(field, i)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,182 @@
+#[cfg(feature = "serde")]
+use serde::{Serialize, Serializer, Deserialize, Deserializer, de};
+#[cfg(feature = "serde")]
+use serde::ser::SerializeStruct;
+
+use super::{Algorithm, Engine, Jump, KnownAlgorithm, LongJump, PlusPlus, Scrambled, Scrambler,
+            Seed128, StarStar, StateError, XorShift1024Seed};
+use super::engine::jump_with;
+
+/// The xoroshiro1024 engine.
+///
+/// It has a period of 2^1024 - 1.  `jump` advances it by 2^512 steps and
+/// `long_jump` by 2^768 steps.
+#[derive(Debug, Clone)]
+pub struct XoroShiro1024Engine {
+    s: [u64; 16],
+    p: usize,
+}
+
+impl XoroShiro1024Engine {
+    /// Return the words `s0` and `s15` the output is computed from.
+    #[inline]
+    fn s0_s15(&self) -> (u64, u64) {
+        (self.s[(self.p + 1) & 15], self.s[self.p])
+    }
+}
+
+impl Engine for XoroShiro1024Engine {
+    type Seed = XorShift1024Seed;
+    type Word = u64;
+    type State = ([u64; 16], usize);
+    const WORDS: usize = 16;
+    const INDEXED: bool = true;
+
+    fn from_words(words: &[u64], index: usize) -> Result<XoroShiro1024Engine, StateError> {
+        if words.iter().all(|&x| x == 0) {
+            return Err(StateError::AllZero);
+        }
+        if index >= 16 {
+            return Err(StateError::IndexOutOfRange(index));
+        }
+        let mut s = [0; 16];
+        s.copy_from_slice(words);
+        Ok(XoroShiro1024Engine {
+            s,
+            p: index,
+        })
+    }
+
+    fn words(&self) -> &[u64] {
+        &self.s
+    }
+
+    fn index(&self) -> usize {
+        self.p
+    }
+
+    /// Return the state `(s, p)`, where `s` are the state words and `p` is the
+    /// index of the current word.
+    fn state(&self) -> ([u64; 16], usize) {
+        (self.s, self.p)
+    }
+
+    fn from_state(state: ([u64; 16], usize)) -> Result<XoroShiro1024Engine, StateError> {
+        XoroShiro1024Engine::from_words(&state.0, state.1)
+    }
+
+    #[inline]
+    fn step(&mut self) {
+        let q = self.p;
+        self.p = (self.p + 1) & 15;
+        let s0 = self.s[self.p];
+        let s15 = self.s[q] ^ s0;
+        self.s[q] = s0.rotate_left(25) ^ s15 ^ (s15 << 27);
+        self.s[self.p] = s15.rotate_left(36);
+    }
+}
+
+impl Seed128 for XoroShiro1024Engine {}
+
+impl Jump for XoroShiro1024Engine {
+    /// Jump forward, equivalently to 2^512 steps.
+    fn jump(&mut self) {
+        const JUMP: [u64; 16] = [0x931197d8e3177f17,
+            0xb59422e0b9138c5f, 0xf06a6afb49d668bb, 0xacb8a6412c8a1401,
+            0x12304ec85f0b3468, 0xb7dfe7079209891e, 0x405b7eec77d9eb14,
+            0x34ead68280c44e4a, 0xe0e4ba3e0ac9e366, 0x8f46eda8348905b7,
+            0x328bf4dbad90d6ff, 0xc8fd6fb31c9effc3, 0xe899d452d4b67652,
+            0x45f387286ade3205, 0x03864f454a8920bd, 0xa68fa28725b1b384];
+        jump_with(self, &JUMP);
+    }
+}
+
+impl LongJump for XoroShiro1024Engine {
+    /// Jump forward, equivalently to 2^768 steps.
+    fn long_jump(&mut self) {
+        const LONG_JUMP: [u64; 16] = [0x7374156360bbf00f,
+            0x4630c2efa3b3c1f6, 0x6654183a892786b1, 0x94f7bfcbfb0f1661,
+            0x27d8243d3d13eb2d, 0x9701730f3dfb300f, 0x2f293baae6f604ad,
+            0xa661831cb60cd8b6, 0x68280c77d9fe008c, 0x50554160f5ba9459,
+            0x2fc20b17ec7b2a9a, 0x49189bbdc8ec9f8f, 0x92a65bca41852cc1,
+            0xf46820dd0509c12a, 0x52b00c35fbf92185, 0x1e5b3b7f589e03c1];
+        jump_with(self, &LONG_JUMP);
+    }
+}
+
+impl Scrambler<XoroShiro1024Engine> for StarStar {
+    #[inline]
+    fn next_u64(engine: &mut XoroShiro1024Engine) -> u64 {
+        let (s0, _) = engine.s0_s15();
+        let r = s0.wrapping_mul(5).rotate_left(7).wrapping_mul(9);
+        engine.step();
+        r
+    }
+}
+
+impl Scrambler<XoroShiro1024Engine> for PlusPlus {
+    #[inline]
+    fn next_u64(engine: &mut XoroShiro1024Engine) -> u64 {
+        let (s0, s15) = engine.s0_s15();
+        let r = s0.wrapping_add(s15).rotate_left(23).wrapping_add(s15);
+        engine.step();
+        r
+    }
+}
+
+/// A xoroshiro1024** random number generator.
+///
+/// This has the same period as `XorShift1024`, but better statistical
+/// properties.
+///
+/// The algorithm used here is translated from [the `xoroshiro1024starstar.c`
+/// reference source code](http://xoshiro.di.unimi.it/xoroshiro1024starstar.c)
+/// by David Blackman and Sebastiano Vigna.
+pub type XoroShiro1024StarStar = Scrambled<XoroShiro1024Engine, StarStar>;
+
+impl KnownAlgorithm for XoroShiro1024StarStar {
+    const ALGORITHM: Algorithm = Algorithm::XoroShiro1024StarStar;
+}
+
+/// A xoroshiro1024++ random number generator.
+///
+/// This has the same period as `XorShift1024`, but better statistical
+/// properties.
+///
+/// The algorithm used here is translated from [the `xoroshiro1024plusplus.c`
+/// reference source code](http://xoshiro.di.unimi.it/xoroshiro1024plusplus.c)
+/// by David Blackman and Sebastiano Vigna.
+pub type XoroShiro1024PlusPlus = Scrambled<XoroShiro1024Engine, PlusPlus>;
+
+impl KnownAlgorithm for XoroShiro1024PlusPlus {
+    const ALGORITHM: Algorithm = Algorithm::XoroShiro1024PlusPlus;
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for XoroShiro1024Engine {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer
+    {
+        let mut state = serializer.serialize_struct("XoroShiro1024", 2)?;
+        state.serialize_field("s", &self.s)?;
+        state.serialize_field("p", &self.p)?;
+        state.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for XoroShiro1024Engine {
+    fn deserialize<D>(deserializer: D) -> Result<XoroShiro1024Engine, D::Error>
+        where D: Deserializer<'de>
+    {
+        #[derive(Deserialize)]
+        #[serde(rename = "XoroShiro1024")]
+        struct State {
+            s: [u64; 16],
+            p: usize,
+        }
+
+        let state = State::deserialize(deserializer)?;
+        XoroShiro1024Engine::from_state((state.s, state.p)).map_err(de::Error::custom)
+    }
+}
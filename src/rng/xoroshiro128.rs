@@ -1,137 +1,405 @@
-use rand_core;
-use rand_core::{RngCore, SeedableRng};
-use byteorder::{LittleEndian, ByteOrder};
+use std::fmt;
+use std::marker::PhantomData;
 
-use super::SplitMix64;
+#[cfg(feature = "serde")]
+use serde::{Serialize, Serializer, Deserialize, Deserializer, de};
+#[cfg(feature = "serde")]
+use serde::ser::SerializeStruct;
 
-/// A xoroshiro128+ random number generator.
+use super::{Algorithm, Engine, Jump, KnownAlgorithm, LongJump, Plus, PlusPlus, Product, Scrambled,
+            Scrambler, Seed128, Star, StarStar, ParamsError, StateError};
+use super::engine::jump_with;
+use super::gf2;
+
+/// The rotation and shift constants of the xoroshiro128 engine, together with
+/// the matching jump polynomials.
+pub trait XoroShiro128Params {
+    /// The rotation applied to `s0`.
+    const A: u32;
+    /// The shift applied to `s0 ^ s1`.
+    const B: u32;
+    /// The rotation applied to `s0 ^ s1`.
+    const C: u32;
+    /// The jump polynomial for 2^64 steps.
+    const JUMP: [u64; 2];
+    /// The jump polynomial for 2^96 steps.
+    const LONG_JUMP: [u64; 2];
+    /// The type name of xoroshiro128+ with these constants, see
+    /// `Scrambler::LEGACY_NAME`.
+    const PLUS_LEGACY_NAME: Option<&'static str> = None;
+}
+
+/// The original constants (55, 14, 36) of xoroshiro128+.
+#[derive(Debug, Clone, Copy)]
+pub struct XoroShiro128Params2016;
+
+impl XoroShiro128Params for XoroShiro128Params2016 {
+    const A: u32 = 55;
+    const B: u32 = 14;
+    const C: u32 = 36;
+    const JUMP: [u64; 2] = [0xbeac0467eba5facb, 0xd86b048b86aa9922];
+    // Not part of the reference implementation, computed as x^(2^96) modulo
+    // the characteristic polynomial.
+    const LONG_JUMP: [u64; 2] = [0x18f7c399ccebda8d, 0xf2deac28bef3bb07];
+    const PLUS_LEGACY_NAME: Option<&'static str> = Some("XoroShiro128");
+}
+
+/// The constants (24, 16, 37) of the 2018 reference implementations of
+/// xoroshiro128+ and xoroshiro128**.
+#[derive(Debug, Clone, Copy)]
+pub struct XoroShiro128Params2018;
+
+impl XoroShiro128Params for XoroShiro128Params2018 {
+    const A: u32 = 24;
+    const B: u32 = 16;
+    const C: u32 = 37;
+    const JUMP: [u64; 2] = [0xdf900294d8f554a5, 0x170865df4b3201fc];
+    const LONG_JUMP: [u64; 2] = [0xd2a98b26625eee7b, 0xdddf9b1090aa7ac1];
+    const PLUS_LEGACY_NAME: Option<&'static str> = Some("XoroShiro128Plus2018");
+}
+
+/// The constants (49, 21, 28) of the reference implementation of
+/// xoroshiro128++.
+#[derive(Debug, Clone, Copy)]
+pub struct XoroShiro128ParamsPlusPlus;
+
+impl XoroShiro128Params for XoroShiro128ParamsPlusPlus {
+    const A: u32 = 49;
+    const B: u32 = 21;
+    const C: u32 = 28;
+    const JUMP: [u64; 2] = [0x2bd7a6a6e99c2ddc, 0x0992ccaf6a6fca05];
+    const LONG_JUMP: [u64; 2] = [0x360fd5f2cf8d5d99, 0x9c6e6877736c46e3];
+}
+
+/// User-supplied constants (`A`, `B`, `C`) for the xoroshiro128 engine.
 ///
-/// The xoroshiro128+ algorithm is not suitable for cryptographic purposes, but
-/// is very fast and has better statistical properties than `XorShiftRng`.  If
-/// you do not know for sure that it fits your requirements, use a more secure
-/// one such as `IsaacRng` or `OsRng`.
+/// The jump polynomials are computed at compile time from the
+/// characteristic polynomial of the engine.  Constants which do not give the
+/// full period of 2^128 - 1 are rejected at compile time when the engine is
+/// used.  Use `check_xoroshiro128_params` for validating constants at run
+/// time.
 ///
-/// The algorithm used here is translated from [the `xoroshiro128plus.c`
-/// reference source code](http://xorshift.di.unimi.it/xoroshiro128plus.c) by
-/// David Blackman and Sebastiano Vigna.
-#[allow(missing_copy_implementations)]
-#[derive(Debug, Clone)]
-pub struct XoroShiro128 {
-    s0: u64,
-    s1: u64,
-}
-
-impl XoroShiro128 {
-    /// Creates a new `XoroShiro128` instance which is not seeded.
-    ///
-    /// The initial values of this RNG are constants, so all generators created
-    /// by this function will yield the same stream of random numbers. It is
-    /// highly recommended that this is created through `SeedableRng` instead of
-    /// this function.
-    pub fn new_unseeded() -> XoroShiro128 {
+/// ```
+/// # extern crate rand;
+/// # extern crate xoroshiro;
+/// # fn main() {
+/// use rand::RngCore;
+/// use xoroshiro::rng::{Plus, Scrambled, XoroShiro128Engine, XoroShiro128ParamsCustom};
+///
+/// type Rng = Scrambled<XoroShiro128Engine<XoroShiro128ParamsCustom<24, 16, 37>>, Plus>;
+/// let mut rng = Rng::from_seed_u64(0);
+/// rng.next_u64();
+/// rng.jump();
+/// # }
+/// ```
+///
+/// ```compile_fail
+/// # extern crate xoroshiro;
+/// # fn main() {
+/// use xoroshiro::rng::{Plus, Scrambled, XoroShiro128Engine, XoroShiro128ParamsCustom};
+///
+/// // (1, 1, 1) does not give a full period.
+/// type Rng = Scrambled<XoroShiro128Engine<XoroShiro128ParamsCustom<1, 1, 1>>, Plus>;
+/// let rng = Rng::from_seed_u64(0);
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct XoroShiro128ParamsCustom<const A: u32, const B: u32, const C: u32>;
+
+impl<const A: u32, const B: u32, const C: u32> XoroShiro128ParamsCustom<A, B, C> {
+    const CHAR_POLY: u128 = {
+        assert!(gf2::xoroshiro128_in_range(A, B, C),
+            "xoroshiro128 constants must be between 1 and 63.");
+        match gf2::xoroshiro128_char_poly(A, B, C) {
+            Some(p) if gf2::is_primitive(p) => p,
+            _ => panic!("xoroshiro128 constants do not give a full period."),
+        }
+    };
+}
+
+impl<const A: u32, const B: u32, const C: u32> XoroShiro128Params
+    for XoroShiro128ParamsCustom<A, B, C>
+{
+    // Referring to `CHAR_POLY` makes sure the constants are validated whenever
+    // an engine is created.
+    const A: u32 = { let _ = Self::CHAR_POLY; A };
+    const B: u32 = B;
+    const C: u32 = C;
+    const JUMP: [u64; 2] = gf2::jump_poly(64, Self::CHAR_POLY);
+    const LONG_JUMP: [u64; 2] = gf2::jump_poly(96, Self::CHAR_POLY);
+}
+
+/// Check whether the constants (`a`, `b`, `c`) give a xoroshiro128 engine with
+/// the full period of 2^128 - 1.
+///
+/// This computes the characteristic polynomial of the engine over GF(2) and
+/// checks whether it is primitive.
+pub fn check_xoroshiro128_params(a: u32, b: u32, c: u32) -> Result<(), ParamsError> {
+    if !gf2::xoroshiro128_in_range(a, b, c) {
+        return Err(ParamsError::OutOfRange);
+    }
+    match gf2::xoroshiro128_char_poly(a, b, c) {
+        Some(p) if gf2::is_primitive(p) => Ok(()),
+        _ => Err(ParamsError::NotFullPeriod),
+    }
+}
+
+/// The xoroshiro128 engine with the rotation and shift constants `P`.
+///
+/// It has a period of 2^128 - 1.  `jump` advances it by 2^64 steps and
+/// `long_jump` by 2^96 steps.
+pub struct XoroShiro128Engine<P> {
+    s: [u64; 2],
+    params: PhantomData<P>,
+}
+
+impl<P: XoroShiro128Params> XoroShiro128Engine<P> {
+    fn new(s: [u64; 2]) -> XoroShiro128Engine<P> {
+        // Evaluating the constants rejects invalid custom parameters at
+        // compile time, even if the engine is never advanced.
+        let _ = P::A;
+        XoroShiro128Engine {
+            s,
+            params: PhantomData,
+        }
+    }
+}
+
+impl<P> Clone for XoroShiro128Engine<P> {
+    fn clone(&self) -> XoroShiro128Engine<P> {
+        XoroShiro128Engine {
+            s: self.s,
+            params: PhantomData,
+        }
+    }
+}
+
+impl<P> fmt::Debug for XoroShiro128Engine<P> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("XoroShiro128Engine")
+            .field("s0", &self.s[0])
+            .field("s1", &self.s[1])
+            .finish()
+    }
+}
+
+impl<P: XoroShiro128Params> Engine for XoroShiro128Engine<P> {
+    type Seed = [u8; 16];
+    type Word = u64;
+    type State = [u64; 2];
+    const WORDS: usize = 2;
+    const INDEXED: bool = false;
+
+    fn new_unseeded() -> XoroShiro128Engine<P> {
         // These constants were taken from the `XorShiftRng` implementation.
         // The only requirement imposed by the algorithm is that these values
         // cannot be zero everywhere.
-        XoroShiro128 {
-            s0: 0x193a6754a8a7d469,
-            s1: 0x97830e05113ba7bb,
-        }
+        XoroShiro128Engine::new([0x193a6754a8a7d469, 0x97830e05113ba7bb])
     }
 
-    pub fn from_seed_u64(seed: u64) -> XoroShiro128 {
-        let mut rng = SplitMix64::from_seed_u64(seed);
-        XoroShiro128::from_rng(&mut rng).unwrap()
-    }
-
-    /// Jump forward, equivalently to 2^64 calls to `next_u64()`.
-    ///
-    /// This can be used to generate 2^64 non-overlapping subsequences for
-    /// parallel computations.
-    ///
-    /// ```
-    /// # extern crate rand;
-    /// # extern crate xoroshiro;
-    /// # fn main() {
-    /// use rand::SeedableRng;
-    /// use xoroshiro::rng::XoroShiro128;
-    ///
-    /// let rng1 = XoroShiro128::from_seed_u64(0);
-    /// let mut rng2 = rng1.clone();
-    /// rng2.jump();
-    /// let mut rng3 = rng2.clone();
-    /// rng3.jump();
-    /// # }
-    /// ```
-    pub fn jump(&mut self) {
-        const JUMP: [u64; 2] = [0xbeac0467eba5facb, 0xd86b048b86aa9922];
-        let mut s0 = 0;
-        let mut s1 = 0;
-        for j in &JUMP {
-            for b in 0..64 {
-                if (j & 1 << b) != 0 {
-                    s0 ^= self.s0;
-                    s1 ^= self.s1;
-                }
-                self.next_u64();
-            }
+    fn from_words(words: &[u64], index: usize) -> Result<XoroShiro128Engine<P>, StateError> {
+        if words.iter().all(|&x| x == 0) {
+            return Err(StateError::AllZero);
+        }
+        if index != 0 {
+            return Err(StateError::IndexOutOfRange(index));
         }
-        self.s0 = s0;
-        self.s1 = s1;
+        let mut s = [0; 2];
+        s.copy_from_slice(words);
+        Ok(XoroShiro128Engine::new(s))
+    }
+
+    fn words(&self) -> &[u64] {
+        &self.s
+    }
+
+    fn index(&self) -> usize {
+        0
+    }
+
+    /// Return the state `[s0, s1]`.
+    fn state(&self) -> [u64; 2] {
+        self.s
+    }
+
+    fn from_state(state: [u64; 2]) -> Result<XoroShiro128Engine<P>, StateError> {
+        XoroShiro128Engine::from_words(&state, 0)
+    }
+
+    #[inline]
+    fn step(&mut self) {
+        let s0 = self.s[0];
+        let s1 = self.s[1] ^ s0;
+        self.s[0] = s0.rotate_left(P::A) ^ s1 ^ (s1 << P::B);
+        self.s[1] = s1.rotate_left(P::C);
+    }
+
+    fn debug_fields(&self, f: &mut fmt::DebugStruct<'_, '_>) {
+        f.field("s0", &self.s[0]);
+        f.field("s1", &self.s[1]);
+    }
+}
+
+impl<P: XoroShiro128Params> Seed128 for XoroShiro128Engine<P> {}
+
+impl<P: XoroShiro128Params> Jump for XoroShiro128Engine<P> {
+    /// Jump forward, equivalently to 2^64 steps.
+    fn jump(&mut self) {
+        jump_with(self, &P::JUMP);
+    }
+}
+
+impl<P: XoroShiro128Params> LongJump for XoroShiro128Engine<P> {
+    /// Jump forward, equivalently to 2^96 steps.
+    fn long_jump(&mut self) {
+        jump_with(self, &P::LONG_JUMP);
+    }
+}
+
+impl<P: XoroShiro128Params> Scrambler<XoroShiro128Engine<P>> for Plus {
+    const LEGACY_NAME: Option<&'static str> = P::PLUS_LEGACY_NAME;
+
+    #[inline]
+    fn next_u64(engine: &mut XoroShiro128Engine<P>) -> u64 {
+        let r = engine.s[0].wrapping_add(engine.s[1]);
+        engine.step();
+        r
     }
 }
 
-impl RngCore for XoroShiro128 {
+impl<P: XoroShiro128Params> Scrambler<XoroShiro128Engine<P>> for Star {
     #[inline]
-    fn next_u32(&mut self) -> u32 {
-        // The two lowest bits have some linear dependencies, so we use the
-        // upper bits instead.
-        (self.next_u64() >> 32) as u32
+    fn next_u64(engine: &mut XoroShiro128Engine<P>) -> u64 {
+        let r = engine.s[0].wrapping_mul(0x9e3779b97f4a7c13);
+        engine.step();
+        r
     }
+}
 
+impl<P: XoroShiro128Params> Scrambler<XoroShiro128Engine<P>> for Product {
     #[inline]
-    fn next_u64(&mut self) -> u64 {
-        let r = self.s0.wrapping_add(self.s1);
-        self.s1 ^= self.s0;
-        self.s0 = self.s0.rotate_left(55) ^ self.s1 ^ (self.s1 << 14);
-        self.s1 = self.s1.rotate_left(36);
+    fn next_u64(engine: &mut XoroShiro128Engine<P>) -> u64 {
+        let r = engine.s[0].wrapping_mul(engine.s[1]);
+        engine.step();
         r
     }
+}
 
+impl<P: XoroShiro128Params> Scrambler<XoroShiro128Engine<P>> for StarStar {
     #[inline]
-    fn fill_bytes(&mut self, dest: &mut [u8]) {
-        for mut chunk in dest.chunks_mut(8) {
-            if chunk.len() == 8 {
-                LittleEndian::write_u64(&mut chunk, self.next_u64());
-            } else {
-                debug_assert!(chunk.len() < 8);
-                let r = self.next_u64();
-                let mut i = 0;
-                for v in chunk.iter_mut() {
-                    *v = (r >> 8*i) as u8;
-                    i += 1;
-                }
-            }
-        }
+    fn next_u64(engine: &mut XoroShiro128Engine<P>) -> u64 {
+        let r = engine.s[0].wrapping_mul(5).rotate_left(7).wrapping_mul(9);
+        engine.step();
+        r
     }
+}
 
+impl<P: XoroShiro128Params> Scrambler<XoroShiro128Engine<P>> for PlusPlus {
     #[inline]
-    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
-        self.fill_bytes(dest);
-        Ok(())
+    fn next_u64(engine: &mut XoroShiro128Engine<P>) -> u64 {
+        let r = engine.s[0].wrapping_add(engine.s[1]).rotate_left(17)
+            .wrapping_add(engine.s[0]);
+        engine.step();
+        r
     }
 }
 
-impl SeedableRng for XoroShiro128 {
-    type Seed = [u8; 16];
+/// A xoroshiro128+ random number generator.
+///
+/// The xoroshiro128+ algorithm is not suitable for cryptographic purposes, but
+/// is very fast and has better statistical properties than `XorShiftRng`.  If
+/// you do not know for sure that it fits your requirements, use a more secure
+/// one such as `IsaacRng` or `OsRng`.
+///
+/// This generator uses the original rotation and shift constants (55, 14, 36).
+/// The current reference implementation uses (24, 16, 37), which is available
+/// as `XoroShiro128Plus2018`.
+///
+/// The algorithm used here is translated from [the `xoroshiro128plus.c`
+/// reference source code](http://xorshift.di.unimi.it/xoroshiro128plus.c) by
+/// David Blackman and Sebastiano Vigna.
+pub type XoroShiro128 = Scrambled<XoroShiro128Engine<XoroShiro128Params2016>, Plus>;
+
+impl KnownAlgorithm for XoroShiro128 {
+    const ALGORITHM: Algorithm = Algorithm::XoroShiro128Plus;
+}
+
+/// A xoroshiro128+ random number generator using the 2018 parameters.
+///
+/// This is the same algorithm as `XoroShiro128`, but with the rotation and
+/// shift constants (24, 16, 37) of the current reference implementation
+/// instead of the original (55, 14, 36).  Both generators produce different
+/// streams for the same state.
+///
+/// The algorithm used here is translated from [the `xoroshiro128plus.c`
+/// reference source code](http://xoshiro.di.unimi.it/xoroshiro128plus.c) by
+/// David Blackman and Sebastiano Vigna.
+pub type XoroShiro128Plus2018 = Scrambled<XoroShiro128Engine<XoroShiro128Params2018>, Plus>;
+
+impl KnownAlgorithm for XoroShiro128Plus2018 {
+    const ALGORITHM: Algorithm = Algorithm::XoroShiro128Plus2018;
+}
 
-    /// Create a new `XoroShiro128`.  This will panic if `seed` is entirely 0.
-    fn from_seed(seed: [u8; 16]) -> XoroShiro128 {
-        assert!(seed != [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0],
-            "XoroShiro128::from_seed called with an all zero seed.");
+/// A xoroshiro128* random number generator.
+///
+/// This uses the xoroshiro128 engine with the 2018 constants (24, 16, 37) and
+/// multiplies `s0` by `0x9e3779b97f4a7c13`, as described in the paper
+/// "Scrambled linear pseudorandom number generators" by David Blackman and
+/// Sebastiano Vigna.
+pub type XoroShiro128Star = Scrambled<XoroShiro128Engine<XoroShiro128Params2018>, Star>;
+
+impl KnownAlgorithm for XoroShiro128Star {
+    const ALGORITHM: Algorithm = Algorithm::XoroShiro128Star;
+}
+
+/// A xoroshiro128** random number generator.
+///
+/// The algorithm used here is translated from [the `xoroshiro128starstar.c`
+/// reference source code](http://xoshiro.di.unimi.it/xoroshiro128starstar.c)
+/// by David Blackman and Sebastiano Vigna.
+pub type XoroShiro128StarStar = Scrambled<XoroShiro128Engine<XoroShiro128Params2018>, StarStar>;
+
+impl KnownAlgorithm for XoroShiro128StarStar {
+    const ALGORITHM: Algorithm = Algorithm::XoroShiro128StarStar;
+}
 
-        XoroShiro128 {
-            s0: LittleEndian::read_u64(&seed[..8]),
-            s1: LittleEndian::read_u64(&seed[8..]),
+/// A xoroshiro128++ random number generator.
+///
+/// The algorithm used here is translated from [the `xoroshiro128plusplus.c`
+/// reference source code](http://xoshiro.di.unimi.it/xoroshiro128plusplus.c)
+/// by David Blackman and Sebastiano Vigna.
+pub type XoroShiro128PlusPlus =
+    Scrambled<XoroShiro128Engine<XoroShiro128ParamsPlusPlus>, PlusPlus>;
+
+impl KnownAlgorithm for XoroShiro128PlusPlus {
+    const ALGORITHM: Algorithm = Algorithm::XoroShiro128PlusPlus;
+}
+
+#[cfg(feature = "serde")]
+impl<P> Serialize for XoroShiro128Engine<P> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer
+    {
+        let mut state = serializer.serialize_struct("XoroShiro128", 2)?;
+        state.serialize_field("s0", &self.s[0])?;
+        state.serialize_field("s1", &self.s[1])?;
+        state.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, P: XoroShiro128Params> Deserialize<'de> for XoroShiro128Engine<P> {
+    fn deserialize<D>(deserializer: D) -> Result<XoroShiro128Engine<P>, D::Error>
+        where D: Deserializer<'de>
+    {
+        #[derive(Deserialize)]
+        #[serde(rename = "XoroShiro128")]
+        struct State {
+            s0: u64,
+            s1: u64,
         }
+
+        let state = State::deserialize(deserializer)?;
+        XoroShiro128Engine::from_state([state.s0, state.s1]).map_err(de::Error::custom)
     }
 }
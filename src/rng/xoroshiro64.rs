@@ -0,0 +1,159 @@
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Serialize, Serializer, Deserialize, Deserializer, de};
+#[cfg(feature = "serde")]
+use serde::ser::SerializeStruct;
+
+use super::{Algorithm, Engine, KnownAlgorithm, Scrambled, Scrambler, Star, StarStar,
+            StateError};
+use super::scrambler::next_u64_via_u32;
+
+/// The xoroshiro64 engine with 32-bit words.
+///
+/// It has a period of 2^64 - 1.  There are no jump functions for this engine.
+#[derive(Clone)]
+pub struct XoroShiro64Engine {
+    s: [u32; 2],
+}
+
+impl fmt::Debug for XoroShiro64Engine {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("XoroShiro64Engine")
+            .field("s0", &self.s[0])
+            .field("s1", &self.s[1])
+            .finish()
+    }
+}
+
+impl Engine for XoroShiro64Engine {
+    type Seed = [u8; 8];
+    type Word = u32;
+    type State = [u32; 2];
+    const WORDS: usize = 2;
+    const INDEXED: bool = false;
+
+    fn from_words(words: &[u32], index: usize) -> Result<XoroShiro64Engine, StateError> {
+        if words.iter().all(|&x| x == 0) {
+            return Err(StateError::AllZero);
+        }
+        if index != 0 {
+            return Err(StateError::IndexOutOfRange(index));
+        }
+        let mut s = [0; 2];
+        s.copy_from_slice(words);
+        Ok(XoroShiro64Engine { s })
+    }
+
+    fn words(&self) -> &[u32] {
+        &self.s
+    }
+
+    fn index(&self) -> usize {
+        0
+    }
+
+    /// Return the state `[s0, s1]`.
+    fn state(&self) -> [u32; 2] {
+        self.s
+    }
+
+    fn from_state(state: [u32; 2]) -> Result<XoroShiro64Engine, StateError> {
+        XoroShiro64Engine::from_words(&state, 0)
+    }
+
+    #[inline]
+    fn step(&mut self) {
+        let s0 = self.s[0];
+        let s1 = self.s[1] ^ s0;
+        self.s[0] = s0.rotate_left(26) ^ s1 ^ (s1 << 9);
+        self.s[1] = s1.rotate_left(13);
+    }
+}
+
+impl Scrambler<XoroShiro64Engine> for Star {
+    #[inline]
+    fn next_u32(engine: &mut XoroShiro64Engine) -> u32 {
+        let r = engine.s[0].wrapping_mul(0x9e3779bb);
+        engine.step();
+        r
+    }
+
+    #[inline]
+    fn next_u64(engine: &mut XoroShiro64Engine) -> u64 {
+        next_u64_via_u32::<Star, _>(engine)
+    }
+}
+
+impl Scrambler<XoroShiro64Engine> for StarStar {
+    #[inline]
+    fn next_u32(engine: &mut XoroShiro64Engine) -> u32 {
+        let r = engine.s[0].wrapping_mul(0x9e3779bb).rotate_left(5).wrapping_mul(5);
+        engine.step();
+        r
+    }
+
+    #[inline]
+    fn next_u64(engine: &mut XoroShiro64Engine) -> u64 {
+        next_u64_via_u32::<StarStar, _>(engine)
+    }
+}
+
+/// A xoroshiro64* random number generator.
+///
+/// This generator works on 32-bit words, making it fast on 32-bit targets.
+/// `next_u64` is composed from two 32-bit outputs.  The lowest bits of the
+/// output have low linear complexity, so this should only be used for
+/// generating floating-point numbers.
+///
+/// The algorithm used here is translated from [the `xoroshiro64star.c`
+/// reference source code](http://xoshiro.di.unimi.it/xoroshiro64star.c) by
+/// David Blackman and Sebastiano Vigna.
+pub type XoroShiro64Star = Scrambled<XoroShiro64Engine, Star>;
+
+impl KnownAlgorithm for XoroShiro64Star {
+    const ALGORITHM: Algorithm = Algorithm::XoroShiro64Star;
+}
+
+/// A xoroshiro64** random number generator.
+///
+/// This generator works on 32-bit words, making it fast on 32-bit targets.
+/// `next_u64` is composed from two 32-bit outputs.
+///
+/// The algorithm used here is translated from [the `xoroshiro64starstar.c`
+/// reference source code](http://xoshiro.di.unimi.it/xoroshiro64starstar.c) by
+/// David Blackman and Sebastiano Vigna.
+pub type XoroShiro64StarStar = Scrambled<XoroShiro64Engine, StarStar>;
+
+impl KnownAlgorithm for XoroShiro64StarStar {
+    const ALGORITHM: Algorithm = Algorithm::XoroShiro64StarStar;
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for XoroShiro64Engine {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer
+    {
+        let mut state = serializer.serialize_struct("XoroShiro64", 2)?;
+        state.serialize_field("s0", &self.s[0])?;
+        state.serialize_field("s1", &self.s[1])?;
+        state.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for XoroShiro64Engine {
+    fn deserialize<D>(deserializer: D) -> Result<XoroShiro64Engine, D::Error>
+        where D: Deserializer<'de>
+    {
+        #[derive(Deserialize)]
+        #[serde(rename = "XoroShiro64")]
+        struct State {
+            s0: u32,
+            s1: u32,
+        }
+
+        let state = State::deserialize(deserializer)?;
+        XoroShiro64Engine::from_state([state.s0, state.s1]).map_err(de::Error::custom)
+    }
+}
@@ -0,0 +1,235 @@
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Serialize, Serializer, Deserialize, Deserializer, de};
+#[cfg(feature = "serde")]
+use serde::ser::SerializeStruct;
+
+use super::{Algorithm, Engine, Jump, KnownAlgorithm, Plus, Scrambled, Scrambler, Seed128,
+            StateError};
+use super::engine::jump_with;
+use hash::{Finalizer, Murmur3};
+
+/// The xorshift128 engine with the shift constants (23, 17, 26).
+///
+/// It has a period of 2^128 - 1.  `jump` advances it by 2^64 steps.
+#[derive(Clone)]
+pub struct XorShift128Engine {
+    s: [u64; 2],
+}
+
+impl fmt::Debug for XorShift128Engine {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("XorShift128Engine")
+            .field("s0", &self.s[0])
+            .field("s1", &self.s[1])
+            .finish()
+    }
+}
+
+impl Engine for XorShift128Engine {
+    type Seed = [u8; 16];
+    type Word = u64;
+    type State = [u64; 2];
+    const WORDS: usize = 2;
+    const INDEXED: bool = false;
+
+    fn from_words(words: &[u64], index: usize) -> Result<XorShift128Engine, StateError> {
+        if words.iter().all(|&x| x == 0) {
+            return Err(StateError::AllZero);
+        }
+        if index != 0 {
+            return Err(StateError::IndexOutOfRange(index));
+        }
+        let mut s = [0; 2];
+        s.copy_from_slice(words);
+        Ok(XorShift128Engine { s })
+    }
+
+    fn words(&self) -> &[u64] {
+        &self.s
+    }
+
+    fn index(&self) -> usize {
+        0
+    }
+
+    /// Return the state `[s0, s1]`.
+    fn state(&self) -> [u64; 2] {
+        self.s
+    }
+
+    fn from_state(state: [u64; 2]) -> Result<XorShift128Engine, StateError> {
+        XorShift128Engine::from_words(&state, 0)
+    }
+
+    #[inline]
+    fn step(&mut self) {
+        let mut s1 = self.s[0];
+        let s0 = self.s[1];
+        self.s[0] = s0;
+        s1 ^= s1 << 23;
+        self.s[1] = s1 ^ s0 ^ (s1 >> 17) ^ (s0 >> 26);
+    }
+}
+
+impl Seed128 for XorShift128Engine {}
+
+impl Jump for XorShift128Engine {
+    /// Jump forward, equivalently to 2^64 steps.
+    fn jump(&mut self) {
+        // Not part of the reference implementation, which uses different
+        // shift constants.  Computed as x^(2^64) modulo the characteristic
+        // polynomial.
+        const JUMP: [u64; 2] = [0x8c405782bca686ad, 0xc44f35946fef49c6];
+        jump_with(self, &JUMP);
+    }
+}
+
+impl Scrambler<XorShift128Engine> for Plus {
+    #[inline]
+    fn next_u64(engine: &mut XorShift128Engine) -> u64 {
+        engine.step();
+        engine.s[0].wrapping_add(engine.s[1])
+    }
+}
+
+/// A xorshift128+ random number generator.
+///
+/// This is the original version of xorshift128+ with the shift constants
+/// (23, 17, 26), which is used by the JavaScript engines V8, SpiderMonkey and
+/// JavaScriptCore.  It is superseded by `XoroShiro128`.  See `V8MathRandom`
+/// for reproducing `Math.random()` of V8.
+///
+/// The algorithm used here is translated from the paper "Further scramblings
+/// of Marsaglia's xorshift generators" by Sebastiano Vigna.
+pub type XorShift128Plus = Scrambled<XorShift128Engine, Plus>;
+
+impl KnownAlgorithm for XorShift128Plus {
+    const ALGORITHM: Algorithm = Algorithm::XorShift128Plus;
+}
+
+/// The number of doubles V8 generates at once.
+const V8_CACHE_SIZE: usize = 64;
+
+/// The `Math.random()` stream of the V8 JavaScript engine.
+///
+/// V8 advances a xorshift128 engine 64 times at once, converts the first state
+/// word after each step to a double in [0, 1) and stores the doubles in a
+/// cache.  `Math.random()` then returns the cached doubles in reverse order,
+/// refilling the cache when it is exhausted.  This reproduces that stream
+/// exactly.
+///
+/// ```
+/// # extern crate xoroshiro;
+/// # fn main() {
+/// use xoroshiro::rng::V8MathRandom;
+///
+/// // Same as the first `Math.random()` of `node --random_seed=42`.
+/// let mut rng = V8MathRandom::from_seed_u64(42);
+/// assert_eq!(rng.next_f64(), 0.7939112874678715);
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct V8MathRandom {
+    engine: XorShift128Engine,
+    cache: [f64; V8_CACHE_SIZE],
+    index: usize,
+}
+
+impl fmt::Debug for V8MathRandom {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("V8MathRandom")
+            .field("engine", &self.engine)
+            .field("index", &self.index)
+            .finish()
+    }
+}
+
+impl V8MathRandom {
+    /// Create the stream V8 uses for the seed `seed`, as set by its
+    /// `--random_seed` option.
+    ///
+    /// The state is initialized from the MurmurHash3 finalizer of `seed` and
+    /// its complement.
+    pub fn from_seed_u64(seed: u64) -> V8MathRandom {
+        let state = [Murmur3::mix(seed), Murmur3::mix(!seed)];
+        // The finalizer is a bijection, so at most one of the words is zero.
+        V8MathRandom::from_state(state).unwrap()
+    }
+
+    /// Create the stream from the state of V8 when its cache is empty.
+    ///
+    /// The next call to `next_f64` refills the cache.  This fails if the state
+    /// is entirely zero.
+    pub fn from_state(state: [u64; 2]) -> Result<V8MathRandom, StateError> {
+        Ok(V8MathRandom {
+            engine: XorShift128Engine::from_state(state)?,
+            cache: [0.; V8_CACHE_SIZE],
+            index: 0,
+        })
+    }
+
+    /// Return the state of the engine, which is the state after the last
+    /// refill of the cache.
+    pub fn state(&self) -> [u64; 2] {
+        self.engine.state()
+    }
+
+    /// Return the number of doubles remaining in the cache.
+    pub fn cached(&self) -> usize {
+        self.index
+    }
+
+    /// Return the next double, like `Math.random()`.
+    pub fn next_f64(&mut self) -> f64 {
+        if self.index == 0 {
+            self.refill();
+        }
+        self.index -= 1;
+        self.cache[self.index]
+    }
+
+    fn refill(&mut self) {
+        for x in self.cache.iter_mut() {
+            self.engine.step();
+            *x = to_double(self.engine.s[0]);
+        }
+        self.index = V8_CACHE_SIZE;
+    }
+}
+
+/// Convert the upper 52 bits of `x` to a double in [0, 1) like V8.
+#[inline]
+fn to_double(x: u64) -> f64 {
+    f64::from_bits((x >> 12) | 0x3ff0000000000000) - 1.
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for XorShift128Engine {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer
+    {
+        let mut state = serializer.serialize_struct("XorShift128", 2)?;
+        state.serialize_field("s0", &self.s[0])?;
+        state.serialize_field("s1", &self.s[1])?;
+        state.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for XorShift128Engine {
+    fn deserialize<D>(deserializer: D) -> Result<XorShift128Engine, D::Error>
+        where D: Deserializer<'de>
+    {
+        #[derive(Deserialize)]
+        #[serde(rename = "XorShift128")]
+        struct State {
+            s0: u64,
+            s1: u64,
+        }
+
+        let state = State::deserialize(deserializer)?;
+        XorShift128Engine::from_state([state.s0, state.s1]).map_err(de::Error::custom)
+    }
+}
@@ -0,0 +1,178 @@
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Serialize, Serializer, Deserialize, Deserializer, de};
+#[cfg(feature = "serde")]
+use serde::ser::SerializeStruct;
+
+use super::{Algorithm, Engine, Jump, KnownAlgorithm, LongJump, PlusPlus, Scrambled, Scrambler,
+            Seed128, StarStar, StateError};
+use super::engine::jump_with;
+use super::scrambler::next_u64_via_u32;
+
+/// The xoshiro128 engine with 32-bit words.
+///
+/// It has a period of 2^128 - 1.  `jump` advances it by 2^64 steps and
+/// `long_jump` by 2^96 steps.
+#[derive(Clone)]
+pub struct Xoshiro128Engine {
+    s: [u32; 4],
+}
+
+impl fmt::Debug for Xoshiro128Engine {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Xoshiro128Engine")
+            .field("s", &self.s)
+            .finish()
+    }
+}
+
+impl Engine for Xoshiro128Engine {
+    type Seed = [u8; 16];
+    type Word = u32;
+    type State = [u32; 4];
+    const WORDS: usize = 4;
+    const INDEXED: bool = false;
+
+    fn from_words(words: &[u32], index: usize) -> Result<Xoshiro128Engine, StateError> {
+        if words.iter().all(|&x| x == 0) {
+            return Err(StateError::AllZero);
+        }
+        if index != 0 {
+            return Err(StateError::IndexOutOfRange(index));
+        }
+        let mut s = [0; 4];
+        s.copy_from_slice(words);
+        Ok(Xoshiro128Engine { s })
+    }
+
+    fn words(&self) -> &[u32] {
+        &self.s
+    }
+
+    fn index(&self) -> usize {
+        0
+    }
+
+    /// Return the state `[s0, s1, s2, s3]`.
+    fn state(&self) -> [u32; 4] {
+        self.s
+    }
+
+    fn from_state(state: [u32; 4]) -> Result<Xoshiro128Engine, StateError> {
+        Xoshiro128Engine::from_words(&state, 0)
+    }
+
+    #[inline]
+    fn step(&mut self) {
+        let t = self.s[1] << 9;
+        self.s[2] ^= self.s[0];
+        self.s[3] ^= self.s[1];
+        self.s[1] ^= self.s[2];
+        self.s[0] ^= self.s[3];
+        self.s[2] ^= t;
+        self.s[3] = self.s[3].rotate_left(11);
+    }
+}
+
+impl Seed128 for Xoshiro128Engine {}
+
+impl Jump for Xoshiro128Engine {
+    /// Jump forward, equivalently to 2^64 steps.
+    fn jump(&mut self) {
+        const JUMP: [u32; 4] = [0x8764000b, 0xf542d2d3, 0x6fa035c3, 0x77f2db5b];
+        jump_with(self, &JUMP);
+    }
+}
+
+impl LongJump for Xoshiro128Engine {
+    /// Jump forward, equivalently to 2^96 steps.
+    fn long_jump(&mut self) {
+        const LONG_JUMP: [u32; 4] = [0xb523952e, 0x0b6f099f, 0xccf5a0ef, 0x1c580662];
+        jump_with(self, &LONG_JUMP);
+    }
+}
+
+impl Scrambler<Xoshiro128Engine> for StarStar {
+    #[inline]
+    fn next_u32(engine: &mut Xoshiro128Engine) -> u32 {
+        let r = engine.s[1].wrapping_mul(5).rotate_left(7).wrapping_mul(9);
+        engine.step();
+        r
+    }
+
+    #[inline]
+    fn next_u64(engine: &mut Xoshiro128Engine) -> u64 {
+        next_u64_via_u32::<StarStar, _>(engine)
+    }
+}
+
+impl Scrambler<Xoshiro128Engine> for PlusPlus {
+    #[inline]
+    fn next_u32(engine: &mut Xoshiro128Engine) -> u32 {
+        let r = engine.s[0].wrapping_add(engine.s[3]).rotate_left(7)
+            .wrapping_add(engine.s[0]);
+        engine.step();
+        r
+    }
+
+    #[inline]
+    fn next_u64(engine: &mut Xoshiro128Engine) -> u64 {
+        next_u64_via_u32::<PlusPlus, _>(engine)
+    }
+}
+
+/// A xoshiro128** random number generator.
+///
+/// This generator works on 32-bit words, making it fast on 32-bit targets.
+/// `next_u64` is composed from two 32-bit outputs.
+///
+/// The algorithm used here is translated from [the `xoshiro128starstar.c`
+/// reference source code](http://xoshiro.di.unimi.it/xoshiro128starstar.c) by
+/// David Blackman and Sebastiano Vigna.
+pub type Xoshiro128StarStar = Scrambled<Xoshiro128Engine, StarStar>;
+
+impl KnownAlgorithm for Xoshiro128StarStar {
+    const ALGORITHM: Algorithm = Algorithm::Xoshiro128StarStar;
+}
+
+/// A xoshiro128++ random number generator.
+///
+/// This generator works on 32-bit words, making it fast on 32-bit targets.
+/// `next_u64` is composed from two 32-bit outputs.
+///
+/// The algorithm used here is translated from [the `xoshiro128plusplus.c`
+/// reference source code](http://xoshiro.di.unimi.it/xoshiro128plusplus.c) by
+/// David Blackman and Sebastiano Vigna.
+pub type Xoshiro128PlusPlus = Scrambled<Xoshiro128Engine, PlusPlus>;
+
+impl KnownAlgorithm for Xoshiro128PlusPlus {
+    const ALGORITHM: Algorithm = Algorithm::Xoshiro128PlusPlus;
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for Xoshiro128Engine {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer
+    {
+        let mut state = serializer.serialize_struct("Xoshiro128", 1)?;
+        state.serialize_field("s", &self.s)?;
+        state.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Xoshiro128Engine {
+    fn deserialize<D>(deserializer: D) -> Result<Xoshiro128Engine, D::Error>
+        where D: Deserializer<'de>
+    {
+        #[derive(Deserialize)]
+        #[serde(rename = "Xoshiro128")]
+        struct State {
+            s: [u32; 4],
+        }
+
+        let state = State::deserialize(deserializer)?;
+        Xoshiro128Engine::from_state(state.s).map_err(de::Error::custom)
+    }
+}
@@ -0,0 +1,401 @@
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Serialize, Serializer, Deserialize, Deserializer, de};
+#[cfg(feature = "serde")]
+use serde::ser::SerializeStruct;
+
+use super::{Algorithm, Engine, Jump, KnownAlgorithm, LongJump, Plus, PlusPlus, Scrambled,
+            Scrambler, Seed128, StarStar, StateError};
+use super::engine::jump_with;
+use super::float::{f32_closed_open, f64_closed_open};
+
+/// The xoshiro256 engine.
+///
+/// It has a period of 2^256 - 1.  `jump` advances it by 2^128 steps and
+/// `long_jump` by 2^192 steps.
+#[derive(Clone)]
+pub struct Xoshiro256Engine {
+    s: [u64; 4],
+}
+
+impl fmt::Debug for Xoshiro256Engine {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Xoshiro256Engine")
+            .field("s", &self.s)
+            .finish()
+    }
+}
+
+impl Engine for Xoshiro256Engine {
+    type Seed = [u8; 32];
+    type Word = u64;
+    type State = [u64; 4];
+    const WORDS: usize = 4;
+    const INDEXED: bool = false;
+
+    fn from_words(words: &[u64], index: usize) -> Result<Xoshiro256Engine, StateError> {
+        if words.iter().all(|&x| x == 0) {
+            return Err(StateError::AllZero);
+        }
+        if index != 0 {
+            return Err(StateError::IndexOutOfRange(index));
+        }
+        let mut s = [0; 4];
+        s.copy_from_slice(words);
+        Ok(Xoshiro256Engine { s })
+    }
+
+    fn words(&self) -> &[u64] {
+        &self.s
+    }
+
+    fn index(&self) -> usize {
+        0
+    }
+
+    /// Return the state `[s0, s1, s2, s3]`.
+    fn state(&self) -> [u64; 4] {
+        self.s
+    }
+
+    fn from_state(state: [u64; 4]) -> Result<Xoshiro256Engine, StateError> {
+        Xoshiro256Engine::from_words(&state, 0)
+    }
+
+    #[inline]
+    fn step(&mut self) {
+        let t = self.s[1] << 17;
+        self.s[2] ^= self.s[0];
+        self.s[3] ^= self.s[1];
+        self.s[1] ^= self.s[2];
+        self.s[0] ^= self.s[3];
+        self.s[2] ^= t;
+        self.s[3] = self.s[3].rotate_left(45);
+    }
+}
+
+impl Seed128 for Xoshiro256Engine {}
+
+impl Jump for Xoshiro256Engine {
+    /// Jump forward, equivalently to 2^128 steps.
+    fn jump(&mut self) {
+        const JUMP: [u64; 4] = [0x180ec6d33cfd0aba, 0xd5a61266f0c9392c,
+            0xa9582618e03fc9aa, 0x39abdc4529b1661c];
+        jump_with(self, &JUMP);
+    }
+}
+
+impl LongJump for Xoshiro256Engine {
+    /// Jump forward, equivalently to 2^192 steps.
+    fn long_jump(&mut self) {
+        const LONG_JUMP: [u64; 4] = [0x76e15d3efefdcbbf, 0xc5004e441c522fb3,
+            0x77710069854ee241, 0x39109bb02acbe635];
+        jump_with(self, &LONG_JUMP);
+    }
+}
+
+impl Scrambler<Xoshiro256Engine> for Plus {
+    #[inline]
+    fn next_u64(engine: &mut Xoshiro256Engine) -> u64 {
+        let r = engine.s[0].wrapping_add(engine.s[3]);
+        engine.step();
+        r
+    }
+}
+
+impl Scrambler<Xoshiro256Engine> for PlusPlus {
+    #[inline]
+    fn next_u64(engine: &mut Xoshiro256Engine) -> u64 {
+        let r = engine.s[0].wrapping_add(engine.s[3]).rotate_left(23)
+            .wrapping_add(engine.s[0]);
+        engine.step();
+        r
+    }
+}
+
+impl Scrambler<Xoshiro256Engine> for StarStar {
+    #[inline]
+    fn next_u64(engine: &mut Xoshiro256Engine) -> u64 {
+        let r = engine.s[1].wrapping_mul(5).rotate_left(7).wrapping_mul(9);
+        engine.step();
+        r
+    }
+}
+
+/// A xoshiro256+ random number generator.
+///
+/// The lowest bits of the output have low linear complexity, so this should
+/// only be used for generating floating-point numbers.
+///
+/// The algorithm used here is translated from [the `xoshiro256plus.c`
+/// reference source code](http://xoshiro.di.unimi.it/xoshiro256plus.c) by
+/// David Blackman and Sebastiano Vigna.
+pub type Xoshiro256Plus = Scrambled<Xoshiro256Engine, Plus>;
+
+impl KnownAlgorithm for Xoshiro256Plus {
+    const ALGORITHM: Algorithm = Algorithm::Xoshiro256Plus;
+}
+
+/// A xoshiro256++ random number generator.
+///
+/// The algorithm used here is translated from [the `xoshiro256plusplus.c`
+/// reference source code](http://xoshiro.di.unimi.it/xoshiro256plusplus.c) by
+/// David Blackman and Sebastiano Vigna.
+pub type Xoshiro256PlusPlus = Scrambled<Xoshiro256Engine, PlusPlus>;
+
+impl KnownAlgorithm for Xoshiro256PlusPlus {
+    const ALGORITHM: Algorithm = Algorithm::Xoshiro256PlusPlus;
+}
+
+/// A xoshiro256** random number generator.
+///
+/// The algorithm used here is translated from [the `xoshiro256starstar.c`
+/// reference source code](http://xoshiro.di.unimi.it/xoshiro256starstar.c) by
+/// David Blackman and Sebastiano Vigna.
+pub type Xoshiro256StarStar = Scrambled<Xoshiro256Engine, StarStar>;
+
+impl KnownAlgorithm for Xoshiro256StarStar {
+    const ALGORITHM: Algorithm = Algorithm::Xoshiro256StarStar;
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for Xoshiro256Engine {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer
+    {
+        let mut state = serializer.serialize_struct("Xoshiro256", 1)?;
+        state.serialize_field("s", &self.s)?;
+        state.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Xoshiro256Engine {
+    fn deserialize<D>(deserializer: D) -> Result<Xoshiro256Engine, D::Error>
+        where D: Deserializer<'de>
+    {
+        #[derive(Deserialize)]
+        #[serde(rename = "Xoshiro256")]
+        struct State {
+            s: [u64; 4],
+        }
+
+        let state = State::deserialize(deserializer)?;
+        Xoshiro256Engine::from_state(state.s).map_err(de::Error::custom)
+    }
+}
+
+/// The `math.random` stream of Lua 5.4.
+///
+/// Lua 5.4 uses xoshiro256** and seeds it by setting the state to
+/// `[n1, 0xff, n2, 0]` and discarding 16 outputs.  Floats use the upper 53
+/// bits of an output, integer ranges mask the lower bits and retry until the
+/// result is in range.  This reproduces the conventions of `lmathlib.c` in
+/// Lua 5.4.0 to 5.4.6 for integer seeds.
+///
+/// Since Lua 5.4.4, `math.randomseed()` returns the two seed components it
+/// used, so a randomly seeded stream can be replayed with `from_seed`.
+#[derive(Debug, Clone)]
+pub struct LuaRandom {
+    engine: Xoshiro256Engine,
+}
+
+impl LuaRandom {
+    /// Create the stream of `math.randomseed(n1, n2)`.
+    ///
+    /// `math.randomseed(n)` is equivalent to `n2 = 0`.
+    pub fn from_seed(n1: i64, n2: i64) -> LuaRandom {
+        // The second word is never zero.
+        let mut rng = LuaRandom::from_state([n1 as u64, 0xff, n2 as u64, 0]).unwrap();
+        for _ in 0..16 {
+            rng.engine.step();
+        }
+        rng
+    }
+
+    /// Create the stream from the xoshiro256** state `[s0, s1, s2, s3]`.
+    ///
+    /// This fails if the state is entirely zero.
+    pub fn from_state(state: [u64; 4]) -> Result<LuaRandom, StateError> {
+        Ok(LuaRandom { engine: Xoshiro256Engine::from_state(state)? })
+    }
+
+    /// Return the state `[s0, s1, s2, s3]`.
+    pub fn state(&self) -> [u64; 4] {
+        self.engine.state()
+    }
+
+    /// Return a random integer with all bits random, like `math.random(0)`.
+    pub fn next_i64(&mut self) -> i64 {
+        StarStar::next_u64(&mut self.engine) as i64
+    }
+
+    /// Return a float in [0, 1), like `math.random()`.
+    pub fn next_f64(&mut self) -> f64 {
+        f64_closed_open(StarStar::next_u64(&mut self.engine))
+    }
+
+    /// Return an integer in [1, m], like `math.random(m)`.
+    ///
+    /// This panics if `m` is less than 1.
+    pub fn next_i64_up_to(&mut self, m: i64) -> i64 {
+        self.next_i64_range(1, m)
+    }
+
+    /// Return an integer in [low, up], like `math.random(low, up)`.
+    ///
+    /// This panics if `low` is larger than `up`.
+    pub fn next_i64_range(&mut self, low: i64, up: i64) -> i64 {
+        assert!(low <= up, "interval is empty");
+        let ran = StarStar::next_u64(&mut self.engine);
+        let n = (up as u64).wrapping_sub(low as u64);
+        (self.project(ran, n) as i64).wrapping_add(low)
+    }
+
+    /// Project `ran` into [0, n] like `project` of `lmathlib.c`.
+    fn project(&mut self, mut ran: u64, n: u64) -> u64 {
+        if n & n.wrapping_add(1) == 0 {
+            // `n + 1` is a power of two.
+            return ran & n;
+        }
+        // The smallest `2^b - 1` not smaller than `n`.
+        let lim = u64::MAX >> n.leading_zeros();
+        loop {
+            ran &= lim;
+            if ran <= n {
+                return ran;
+            }
+            ran = StarStar::next_u64(&mut self.engine);
+        }
+    }
+}
+
+/// The `System.Random` stream of .NET 6 and later.
+///
+/// An unseeded `System.Random` in a 64-bit process uses xoshiro256** seeded
+/// from the operating system, so the stream can only be replayed from its
+/// state, which are the fields `_s0` to `_s3` of `XoshiroImpl`.  Seeded
+/// instances use a different legacy algorithm, which is not provided here.
+///
+/// Integer ranges use the upper bits of an output, the fewest needed to cover
+/// the range, and retry until the result is in range.  This reproduces the
+/// conventions of `XoshiroImpl` in .NET 6.
+#[derive(Debug, Clone)]
+pub struct DotNetRandom {
+    engine: Xoshiro256Engine,
+}
+
+impl DotNetRandom {
+    /// Create the stream from the state `[_s0, _s1, _s2, _s3]`.
+    ///
+    /// This fails if the state is entirely zero.
+    pub fn from_state(state: [u64; 4]) -> Result<DotNetRandom, StateError> {
+        Ok(DotNetRandom { engine: Xoshiro256Engine::from_state(state)? })
+    }
+
+    /// Return the state `[_s0, _s1, _s2, _s3]`.
+    pub fn state(&self) -> [u64; 4] {
+        self.engine.state()
+    }
+
+    /// Return the next 64-bit output, like the internal `NextUInt64()`.
+    pub fn next_u64(&mut self) -> u64 {
+        StarStar::next_u64(&mut self.engine)
+    }
+
+    /// Return an integer in [0, `i32::MAX`), like `Next()`.
+    pub fn next_i32(&mut self) -> i32 {
+        loop {
+            let r = self.next_u64() >> 33;
+            if r != i32::MAX as u64 {
+                return r as i32;
+            }
+        }
+    }
+
+    /// Return an integer in [0, max), like `Next(maxValue)`.
+    ///
+    /// This returns 0 if `max` is 0 and panics if `max` is negative.
+    pub fn next_i32_below(&mut self, max: i32) -> i32 {
+        assert!(max >= 0, "max must be non-negative");
+        self.next_below(max as u64) as i32
+    }
+
+    /// Return an integer in [min, max), like `Next(minValue, maxValue)`.
+    ///
+    /// This returns `min` if the range is empty and panics if `min` is larger
+    /// than `max`.
+    pub fn next_i32_range(&mut self, min: i32, max: i32) -> i32 {
+        assert!(min <= max, "min must not be larger than max");
+        let r = self.next_below((i64::from(max) - i64::from(min)) as u64);
+        (r as i64 + i64::from(min)) as i32
+    }
+
+    /// Return an integer in [0, `i64::MAX`), like `NextInt64()`.
+    pub fn next_i64(&mut self) -> i64 {
+        loop {
+            let r = self.next_u64() >> 1;
+            if r != i64::MAX as u64 {
+                return r as i64;
+            }
+        }
+    }
+
+    /// Return an integer in [0, max), like `NextInt64(maxValue)`.
+    ///
+    /// This returns 0 if `max` is 0 and panics if `max` is negative.
+    pub fn next_i64_below(&mut self, max: i64) -> i64 {
+        assert!(max >= 0, "max must be non-negative");
+        self.next_below(max as u64) as i64
+    }
+
+    /// Return an integer in [min, max), like `NextInt64(minValue, maxValue)`.
+    ///
+    /// This returns `min` if the range is empty and panics if `min` is larger
+    /// than `max`.
+    pub fn next_i64_range(&mut self, min: i64, max: i64) -> i64 {
+        assert!(min <= max, "min must not be larger than max");
+        let r = self.next_below(max.wrapping_sub(min) as u64);
+        (r as i64).wrapping_add(min)
+    }
+
+    /// Return a double in [0, 1), like `NextDouble()`.
+    pub fn next_f64(&mut self) -> f64 {
+        f64_closed_open(self.next_u64())
+    }
+
+    /// Return a float in [0, 1), like `NextSingle()`.
+    pub fn next_f32(&mut self) -> f32 {
+        f32_closed_open((self.next_u64() >> 32) as u32)
+    }
+
+    /// Fill `dest` with random bytes, like `NextBytes(buffer)`.
+    ///
+    /// Each output provides 8 little-endian bytes, the remaining bytes of the
+    /// last output are discarded.
+    pub fn fill_bytes(&mut self, dest: &mut [u8]) {
+        for chunk in dest.chunks_mut(8) {
+            let r = self.next_u64();
+            for (i, v) in chunk.iter_mut().enumerate() {
+                *v = (r >> (8*i)) as u8;
+            }
+        }
+    }
+
+    /// Return an integer in [0, n) using the upper bits of the outputs, or 0
+    /// if `n` is at most 1.
+    fn next_below(&mut self, n: u64) -> u64 {
+        if n <= 1 {
+            return 0;
+        }
+        // `Log2Ceiling(n)`, which is at least 1.
+        let bits = 64 - (n - 1).leading_zeros();
+        loop {
+            let r = self.next_u64() >> (64 - bits);
+            if r < n {
+                return r;
+            }
+        }
+    }
+}
@@ -0,0 +1,169 @@
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Serialize, Serializer, Deserialize, Deserializer, de};
+#[cfg(feature = "serde")]
+use serde::ser::SerializeStruct;
+
+use super::{Algorithm, Engine, Jump, KnownAlgorithm, LongJump, PlusPlus, Scrambled, Scrambler,
+            Seed128, StarStar, StateError, Xoshiro512Seed};
+use super::engine::jump_with;
+
+/// The xoshiro512 engine.
+///
+/// It has a period of 2^512 - 1.  `jump` advances it by 2^256 steps and
+/// `long_jump` by 2^384 steps.
+#[derive(Clone)]
+pub struct Xoshiro512Engine {
+    s: [u64; 8],
+}
+
+impl fmt::Debug for Xoshiro512Engine {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Xoshiro512Engine")
+            .field("s", &self.s)
+            .finish()
+    }
+}
+
+impl Engine for Xoshiro512Engine {
+    type Seed = Xoshiro512Seed;
+    type Word = u64;
+    type State = [u64; 8];
+    const WORDS: usize = 8;
+    const INDEXED: bool = false;
+
+    fn from_words(words: &[u64], index: usize) -> Result<Xoshiro512Engine, StateError> {
+        if words.iter().all(|&x| x == 0) {
+            return Err(StateError::AllZero);
+        }
+        if index != 0 {
+            return Err(StateError::IndexOutOfRange(index));
+        }
+        let mut s = [0; 8];
+        s.copy_from_slice(words);
+        Ok(Xoshiro512Engine { s })
+    }
+
+    fn words(&self) -> &[u64] {
+        &self.s
+    }
+
+    fn index(&self) -> usize {
+        0
+    }
+
+    /// Return the state `[s0, ..., s7]`.
+    fn state(&self) -> [u64; 8] {
+        self.s
+    }
+
+    fn from_state(state: [u64; 8]) -> Result<Xoshiro512Engine, StateError> {
+        Xoshiro512Engine::from_words(&state, 0)
+    }
+
+    #[inline]
+    fn step(&mut self) {
+        let t = self.s[1] << 11;
+        self.s[2] ^= self.s[0];
+        self.s[5] ^= self.s[1];
+        self.s[1] ^= self.s[2];
+        self.s[7] ^= self.s[3];
+        self.s[3] ^= self.s[4];
+        self.s[4] ^= self.s[5];
+        self.s[0] ^= self.s[6];
+        self.s[6] ^= self.s[7];
+        self.s[6] ^= t;
+        self.s[7] = self.s[7].rotate_left(21);
+    }
+}
+
+impl Seed128 for Xoshiro512Engine {}
+
+impl Jump for Xoshiro512Engine {
+    /// Jump forward, equivalently to 2^256 steps.
+    fn jump(&mut self) {
+        const JUMP: [u64; 8] = [0x33ed89b6e7a353f9, 0x760083d7955323be,
+            0x2837f2fbb5f22fae, 0x4b8c5674d309511c, 0xb11ac47a7ba28c25,
+            0xf1be7667092bcc1c, 0x53851efdb6df0aaf, 0x1ebbc8b23eaf25db];
+        jump_with(self, &JUMP);
+    }
+}
+
+impl LongJump for Xoshiro512Engine {
+    /// Jump forward, equivalently to 2^384 steps.
+    fn long_jump(&mut self) {
+        const LONG_JUMP: [u64; 8] = [0x11467fef8f921d28, 0xa2a819f2e79c8ea8,
+            0xa8299fc284b3959a, 0xb4d347340ca63ee1, 0x1cb0940bedbff6ce,
+            0xd956c5c4fa1f8e17, 0x915e38fd4eda93bc, 0x5b3ccdfa5d7daca5];
+        jump_with(self, &LONG_JUMP);
+    }
+}
+
+impl Scrambler<Xoshiro512Engine> for StarStar {
+    #[inline]
+    fn next_u64(engine: &mut Xoshiro512Engine) -> u64 {
+        let r = engine.s[1].wrapping_mul(5).rotate_left(7).wrapping_mul(9);
+        engine.step();
+        r
+    }
+}
+
+impl Scrambler<Xoshiro512Engine> for PlusPlus {
+    #[inline]
+    fn next_u64(engine: &mut Xoshiro512Engine) -> u64 {
+        let r = engine.s[0].wrapping_add(engine.s[2]).rotate_left(17)
+            .wrapping_add(engine.s[2]);
+        engine.step();
+        r
+    }
+}
+
+/// A xoshiro512** random number generator.
+///
+/// The algorithm used here is translated from [the `xoshiro512starstar.c`
+/// reference source code](http://xoshiro.di.unimi.it/xoshiro512starstar.c) by
+/// David Blackman and Sebastiano Vigna.
+pub type Xoshiro512StarStar = Scrambled<Xoshiro512Engine, StarStar>;
+
+impl KnownAlgorithm for Xoshiro512StarStar {
+    const ALGORITHM: Algorithm = Algorithm::Xoshiro512StarStar;
+}
+
+/// A xoshiro512++ random number generator.
+///
+/// The algorithm used here is translated from [the `xoshiro512plusplus.c`
+/// reference source code](http://xoshiro.di.unimi.it/xoshiro512plusplus.c) by
+/// David Blackman and Sebastiano Vigna.
+pub type Xoshiro512PlusPlus = Scrambled<Xoshiro512Engine, PlusPlus>;
+
+impl KnownAlgorithm for Xoshiro512PlusPlus {
+    const ALGORITHM: Algorithm = Algorithm::Xoshiro512PlusPlus;
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for Xoshiro512Engine {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer
+    {
+        let mut state = serializer.serialize_struct("Xoshiro512", 1)?;
+        state.serialize_field("s", &self.s)?;
+        state.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Xoshiro512Engine {
+    fn deserialize<D>(deserializer: D) -> Result<Xoshiro512Engine, D::Error>
+        where D: Deserializer<'de>
+    {
+        #[derive(Deserialize)]
+        #[serde(rename = "Xoshiro512")]
+        struct State {
+            s: [u64; 8],
+        }
+
+        let state = State::deserialize(deserializer)?;
+        Xoshiro512Engine::from_state(state.s).map_err(de::Error::custom)
+    }
+}
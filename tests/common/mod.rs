@@ -0,0 +1,24 @@
+//! Helpers shared by the integration tests.
+
+// Not every test uses every helper.
+#![allow(dead_code)]
+
+use xoroshiro::hash::unmix64;
+use xoroshiro::rng::SplitMix64;
+
+/// Create a `SplitMix64` whose next output is `x`.
+pub fn splitmix64_yielding(x: u64) -> SplitMix64 {
+    SplitMix64::from_state(unmix64(x).wrapping_sub(0x9e3779b97f4a7c15))
+}
+
+/// Create a `SplitMix64` whose next outputs are `x` and `y` with some of its
+/// lowest 12 bits changed, by choosing the gamma.
+pub fn splitmix64_yielding2(x: u64, y: u64) -> SplitMix64 {
+    let ux = unmix64(x);
+    (0..0x1000)
+        .map(|low| unmix64(y ^ low).wrapping_sub(ux))
+        .filter(|gamma| gamma % 2 == 1)
+        .map(|gamma| SplitMix64::from_state_gamma(ux.wrapping_sub(gamma), gamma).unwrap())
+        .next()
+        .unwrap()
+}
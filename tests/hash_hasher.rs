@@ -0,0 +1,98 @@
+#![allow(unknown_lints)]
+#![allow(clippy::unreadable_literal)]
+
+extern crate xoroshiro;
+
+use std::collections::HashMap;
+use std::hash::{BuildHasher, Hasher};
+
+use xoroshiro::hash::{mix64, SplitMixBuildHasher, SplitMixHasher};
+
+fn hash_bytes(seed: u64, bytes: &[u8]) -> u64 {
+    let mut h = SplitMixHasher::with_seed(seed);
+    h.write(bytes);
+    h.finish()
+}
+
+#[test]
+fn hasher_stable_output() {
+    // These values are part of the stability guarantee.
+    assert_eq!(SplitMixHasher::default().finish(), 0);
+    assert_eq!(hash_bytes(0, b"hello"), 4050870270638509416);
+    let mut h = SplitMixHasher::with_seed(42);
+    h.write_u64(7);
+    assert_eq!(h.finish(), mix64(mix64(42 ^ 7).wrapping_add(8)));
+    assert_eq!(h.finish(), 14109300917271853224);
+}
+
+#[test]
+fn hasher_split_independent() {
+    let bytes: Vec<u8> = (0..100).collect();
+    let expected = hash_bytes(3, &bytes);
+    for split in 0..bytes.len() {
+        let mut h = SplitMixHasher::with_seed(3);
+        h.write(&bytes[..split]);
+        h.write(&bytes[split..]);
+        assert_eq!(h.finish(), expected, "split at {}", split);
+    }
+
+    // Integer writes are equivalent to writing their little-endian bytes, at
+    // a word boundary and in the middle of a word.
+    for offset in 0..8 {
+        let mut a = SplitMixHasher::with_seed(3);
+        let mut b = SplitMixHasher::with_seed(3);
+        a.write(&bytes[..offset]);
+        b.write(&bytes[..offset]);
+        a.write_u64(0x0123456789abcdef);
+        a.write_u32(0xdeadbeef);
+        a.write_u16(0xcafe);
+        a.write_u8(0x42);
+        a.write_usize(17);
+        a.write_u128(1 << 100);
+        b.write(&0x0123456789abcdefu64.to_le_bytes());
+        b.write(&0xdeadbeefu32.to_le_bytes());
+        b.write(&0xcafeu16.to_le_bytes());
+        b.write(&[0x42]);
+        b.write(&17u64.to_le_bytes());
+        b.write(&(1u128 << 100).to_le_bytes());
+        assert_eq!(a.finish(), b.finish());
+    }
+}
+
+#[test]
+fn hasher_length() {
+    // Trailing zeros change the hash.
+    let hashes: Vec<u64> = (0..20).map(|n| hash_bytes(0, &vec![0; n])).collect();
+    for i in 0..hashes.len() {
+        for j in 0..i {
+            assert_ne!(hashes[i], hashes[j]);
+        }
+    }
+}
+
+#[test]
+fn build_hasher() {
+    let fixed = SplitMixBuildHasher::with_seed(5);
+    assert_eq!(fixed.seed(), 5);
+    let mut a = fixed.build_hasher();
+    let mut b = SplitMixHasher::with_seed(5);
+    a.write_u32(1);
+    b.write_u32(1);
+    assert_eq!(a.finish(), b.finish());
+
+    // A fixed seed gives a reproducible iteration order.
+    let keys: Vec<u64> = (0..1000).map(|i| i * 7919).collect();
+    let mut m1 = HashMap::with_hasher(SplitMixBuildHasher::with_seed(1));
+    let mut m2 = HashMap::with_hasher(SplitMixBuildHasher::with_seed(1));
+    for &k in &keys {
+        m1.insert(k, ());
+        m2.insert(k, ());
+    }
+    let order1: Vec<u64> = m1.keys().cloned().collect();
+    let order2: Vec<u64> = m2.keys().cloned().collect();
+    assert_eq!(order1, order2);
+
+    // Random seeds differ between instances.
+    let seeds: Vec<u64> = (0..4).map(|_| SplitMixBuildHasher::new().seed()).collect();
+    assert!(seeds.windows(2).any(|w| w[0] != w[1]));
+}
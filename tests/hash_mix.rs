@@ -0,0 +1,90 @@
+#![allow(unknown_lints)]
+#![allow(clippy::unreadable_literal)]
+
+extern crate rand;
+extern crate xoroshiro;
+
+use rand::RngCore;
+use xoroshiro::hash::{mix64, unmix64, Finalizer, Moremur, Murmur3, Stafford01, Stafford04,
+                      Stafford13};
+use xoroshiro::rng::SplitMix64;
+
+/// Check that `unmix` inverts `mix`.
+fn check_inverse<F: Finalizer>() {
+    let mut rng = SplitMix64::from_seed_u64(0);
+    for x in (0..64).map(|i| 1 << i).chain(vec![0, u64::MAX]) {
+        assert_eq!(F::unmix(F::mix(x)), x);
+        assert_eq!(F::mix(F::unmix(x)), x);
+    }
+    for _ in 0..10000 {
+        let x = rng.next_u64();
+        assert_eq!(F::unmix(F::mix(x)), x);
+        assert_eq!(F::mix(F::unmix(x)), x);
+    }
+}
+
+/// Check that flipping any input bit flips every output bit with a
+/// probability close to 1/2.
+fn check_avalanche<F: Finalizer>() {
+    const N: usize = 10000;
+    let mut counts = [[0u32; 64]; 64];
+    let mut rng = SplitMix64::from_seed_u64(1);
+    for _ in 0..N {
+        let x = rng.next_u64();
+        let y = F::mix(x);
+        for (i, row) in counts.iter_mut().enumerate() {
+            let d = y ^ F::mix(x ^ (1 << i));
+            for (j, c) in row.iter_mut().enumerate() {
+                *c += ((d >> j) & 1) as u32;
+            }
+        }
+    }
+    for (i, row) in counts.iter().enumerate() {
+        for (j, &c) in row.iter().enumerate() {
+            let p = f64::from(c) / N as f64;
+            assert!((p - 0.5).abs() < 0.05,
+                "input bit {} flips output bit {} with probability {}", i, j, p);
+        }
+    }
+}
+
+#[test]
+fn mix64_inverse() {
+    check_inverse::<Stafford13>();
+    let mut rng = SplitMix64::from_seed_u64(2);
+    for _ in 0..1000 {
+        let x = rng.next_u64();
+        assert_eq!(unmix64(mix64(x)), x);
+        assert_eq!(mix64(x), Stafford13::mix(x));
+    }
+}
+
+#[test]
+fn mix64_is_splitmix64_output() {
+    let seed = 1477776061723855037;
+    let mut rng = SplitMix64::from_seed_u64(seed);
+    assert_eq!(mix64(seed.wrapping_add(0x9e3779b97f4a7c15)), rng.next_u64());
+}
+
+#[test]
+fn murmur3() {
+    // The MurmurHash3 finalizer maps 0 to 0.
+    assert_eq!(Murmur3::mix(0), 0);
+    check_inverse::<Murmur3>();
+    check_avalanche::<Murmur3>();
+}
+
+#[test]
+fn stafford() {
+    check_inverse::<Stafford01>();
+    check_avalanche::<Stafford01>();
+    check_inverse::<Stafford04>();
+    check_avalanche::<Stafford04>();
+    check_avalanche::<Stafford13>();
+}
+
+#[test]
+fn moremur() {
+    check_inverse::<Moremur>();
+    check_avalanche::<Moremur>();
+}
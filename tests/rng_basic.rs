@@ -1,17 +1,18 @@
 #![allow(unknown_lints)]
-#![allow(unreadable_literal)]
+#![allow(clippy::unreadable_literal)]
 
 extern crate byteorder;
 extern crate rand;
 extern crate xoroshiro;
 
 use byteorder::{ByteOrder, LittleEndian};
-use rand::{Rng, SeedableRng};
+use rand::{Rng, RngCore, SeedableRng};
 use rand::distributions::Standard;
 use xoroshiro::rng::{
-    SplitMix64,
-    XoroShiro128,
-    XorShift1024, XorShift1024Seed
+    SplitMix64, SplittableRandom,
+    XoroShiro128, XoroShiro128Plus2018,
+    XorShift1024, XorShift1024Seed,
+    Xoshiro256StarStar,
 };
 
 #[test]
@@ -142,6 +143,126 @@ fn xoroshiro128() {
     }
 }
 
+#[test]
+fn xoroshiro128plus2018() {
+    let seed_u64: u64 = 1477776328140003287;
+    let mut seed = [0; 16];
+    for i in 0..2 {
+        LittleEndian::write_u64(&mut seed[i*8..(i + 1)*8], seed_u64);
+    }
+    // Generated with the reference `xoroshiro128plus.c`.
+    let expected: Vec<u64> = vec![
+        2955552656280006574,
+        13666247938439152157,
+        15058008799748861267,
+        79835546474137347,
+        4974185556193769640,
+        15829964772980275621,
+        10867352770427011377,
+        10979352819451712376,
+        2987246819825465626,
+        17386115049055094197,
+        14061187218916368924,
+        12702195827905166634,
+        15031373512658121674,
+        4505609341651611855,
+        17820081975307584045,
+        6672723521528834387,
+        5472623002302672024,
+        14966154882465126532,
+        10989295740815643554,
+        15470158626150812584,
+    ];
+
+    let mut rng = XoroShiro128Plus2018::from_seed(seed);
+    for (i, &j) in rng.sample_iter::<u64, _>(&Standard).zip(expected.iter()) {
+        assert_eq!(i, j);
+    }
+}
+
+#[test]
+fn debug_format() {
+    // The generators which predate `Scrambled` keep their `Debug` output.
+    assert_eq!(format!("{:?}", XoroShiro128::new_unseeded()),
+               "XoroShiro128 { s0: 1817879012901901417, s1: 10917585336602961851 }");
+    assert_eq!(format!("{:?}", XoroShiro128Plus2018::new_unseeded()),
+               "XoroShiro128Plus2018 { s0: 1817879012901901417, s1: 10917585336602961851 }");
+    let mut seed = XorShift1024Seed::default();
+    seed.0[0] = 1;
+    let rng = XorShift1024::from_seed(seed);
+    let mut words = [0u64; 16];
+    words[0] = 1;
+    assert_eq!(format!("{:?}", rng), format!("XorShift1024 {{ s: {:?}, p: 0 }}", words));
+    let rng = Xoshiro256StarStar::new_unseeded();
+    assert!(format!("{:?}", rng).starts_with("Scrambled { engine: Xoshiro256Engine {"));
+}
+
+#[test]
+#[should_panic(expected = "XoroShiro128::from_seed called with an all zero seed.")]
+fn xoroshiro128_zero_seed_message() {
+    XoroShiro128::from_seed([0; 16]);
+}
+
+#[test]
+#[should_panic(expected = "XorShift1024::from_seed called with an all zero seed.")]
+fn xorshift1024_zero_seed_message() {
+    XorShift1024::from_seed(XorShift1024Seed::default());
+}
+
+#[test]
+#[should_panic(expected = "Scrambled::from_seed called with an all zero seed.")]
+fn scrambled_zero_seed_message() {
+    Xoshiro256StarStar::from_seed([0; 32]);
+}
+
+#[test]
+fn xoroshiro128plus2018_jumps() {
+    let seed_u64: u64 = 1477776328140003287;
+    let mut seed = [0; 16];
+    for i in 0..2 {
+        LittleEndian::write_u64(&mut seed[i*8..(i + 1)*8], seed_u64);
+    }
+    // Generated with the reference `xoroshiro128plus.c`, jumping after 20 and
+    // long jumping after 30 calls to `next`.
+    let expected_jump: Vec<u64> = vec![
+        8676596176554197095,
+        1134415555636283157,
+        18003571867433940220,
+        4127644051261530547,
+        10025525886033318596,
+        2939036915055213746,
+        14685202226677351565,
+        10160516507679617684,
+        5814160141873242124,
+        6327410084737854050,
+    ];
+    let expected_long_jump: Vec<u64> = vec![
+        3914952329872633741,
+        9112582898247207197,
+        4193615830618554346,
+        2927474981978450300,
+        5880944852227808337,
+        17356522379804215956,
+        13402826688188798947,
+        2592425250623346991,
+        17603143813888587942,
+        12286781844379146136,
+    ];
+
+    let mut rng = XoroShiro128Plus2018::from_seed(seed);
+    for _ in 0..20 {
+        rng.next_u64();
+    }
+    rng.jump();
+    for &j in &expected_jump {
+        assert_eq!(rng.next_u64(), j);
+    }
+    rng.long_jump();
+    for &j in &expected_long_jump {
+        assert_eq!(rng.next_u64(), j);
+    }
+}
+
 #[test]
 fn xorshift1024() {
     let seed_u64: u64 = 1477777179826044140;
@@ -225,3 +346,118 @@ fn bool_xoroshiro() {
     println!("{}", ratio);
     assert!((ratio - 0.5) < 1e-4);
 }
+
+#[test]
+fn splitmix64_split() {
+    // Splitting matches `SplittableRandom`, which uses the same finalizer.
+    let mut rng = SplitMix64::from_state(42);
+    let mut java = SplittableRandom::from_seed(42);
+    let mut child = rng.split();
+    let mut java_child = java.split();
+    assert_eq!(child.gamma(), java_child.state()[1]);
+    assert_ne!(child.gamma(), rng.gamma());
+    assert_eq!(child.gamma() & 1, 1);
+    for _ in 0..10 {
+        assert_eq!(child.next_u64(), java_child.next_i64() as u64);
+        assert_eq!(rng.next_u64(), java.next_i64() as u64);
+    }
+
+    let mut grandchild = child.split();
+    let mut java_grandchild = java_child.split();
+    for _ in 0..10 {
+        assert_eq!(grandchild.next_u64(), java_grandchild.next_i64() as u64);
+    }
+}
+
+#[test]
+fn splitmix64_random_access() {
+    let seed = 1477776061723855037;
+    let mut rng = SplitMix64::from_seed_u64(seed);
+    let mut expected = [0; 100];
+    for (i, e) in expected.iter_mut().enumerate() {
+        assert_eq!(rng.position(), i as u64);
+        *e = rng.next_u64();
+        assert_eq!(SplitMix64::at(seed, i as u64), *e);
+    }
+    assert_eq!(expected[0], 1985237415132408290);
+
+    let mut range = [0; 37];
+    SplitMix64::fill_range(seed, 50, &mut range);
+    assert_eq!(&range[..], &expected[50..87]);
+
+    let mut rng = SplitMix64::from_seed_u64(seed);
+    rng.advance(42);
+    assert_eq!(rng.position(), 42);
+    assert_eq!(rng.next_u64(), expected[42]);
+    rng.advance(0u64.wrapping_sub(10));
+    assert_eq!(rng.position(), 33);
+    assert_eq!(rng.next_u64(), expected[33]);
+
+    // The position also works with the gamma of split generators.
+    let mut child = rng.split();
+    assert_eq!(rng.position(), 36);
+    let mut copy = child.clone();
+    copy.advance(1000);
+    for _ in 0..1000 {
+        child.next_u64();
+    }
+    assert_eq!(child.position(), 1000);
+    assert_eq!(copy.position(), 1000);
+    assert_eq!(child.next_u64(), copy.next_u64());
+
+    // A restored generator counts from the restored state, unless the start
+    // state is given.
+    let start = SplitMix64::from_seed_u64(seed).state();
+    let restored = SplitMix64::from_state_gamma(rng.state(), rng.gamma()).unwrap();
+    assert_eq!(restored.position(), 0);
+    assert_eq!(restored.position_from(start), 36);
+    assert_eq!(rng.position_from(start), rng.position());
+}
+
+#[test]
+fn atomic_splitmix64() {
+    use std::sync::Arc;
+    use std::thread;
+    use xoroshiro::rng::AtomicSplitMix64;
+
+    let seed = 1477776061723855037;
+    let rng = Arc::new(AtomicSplitMix64::from_seed_u64(seed));
+    let handles: Vec<_> = (0..4).map(|_| {
+        let rng = rng.clone();
+        thread::spawn(move || {
+            let mut r = &*rng;
+            (0..1000).map(|_| r.next_u64()).collect::<Vec<u64>>()
+        })
+    }).collect();
+    let mut outputs: Vec<u64> = handles.into_iter()
+        .flat_map(|h| h.join().unwrap())
+        .collect();
+    outputs.sort();
+
+    let mut sequential = SplitMix64::from_seed_u64(seed);
+    let mut expected: Vec<u64> = (0..4000).map(|_| sequential.next_u64()).collect();
+    expected.sort();
+    assert_eq!(outputs, expected);
+
+    let rng = Arc::try_unwrap(rng).unwrap().into_inner();
+    assert_eq!(rng.state(), sequential.state());
+}
+
+#[test]
+fn atomic_splitmix64_fill_bytes() {
+    use xoroshiro::rng::AtomicSplitMix64;
+
+    let mut parent = SplitMix64::from_seed_u64(3);
+    let child = parent.split();
+    let mut sequential = child.clone();
+    let atomic = AtomicSplitMix64::from(child);
+    assert_eq!(atomic.gamma(), sequential.gamma());
+
+    let mut a = [0; 21];
+    let mut b = [0; 21];
+    (&atomic).fill_bytes(&mut a);
+    sequential.fill_bytes(&mut b);
+    assert_eq!(a, b);
+    assert_eq!((&atomic).next_u64(), sequential.next_u64());
+    assert_eq!(atomic.state(), sequential.state());
+}
@@ -0,0 +1,81 @@
+#![allow(unknown_lints)]
+#![allow(clippy::unreadable_literal)]
+
+extern crate rand;
+extern crate xoroshiro;
+
+use rand::RngCore;
+use xoroshiro::rng::{BitBuffer, SplitMix64, XoroShiro128};
+
+#[test]
+fn bit_buffer_stream() {
+    let mut reference = XoroShiro128::from_seed_u64(1);
+    let mut rng = BitBuffer::new(reference.clone());
+
+    // The bits of the outputs, most significant first.
+    let mut stream = Vec::new();
+    for _ in 0..70 {
+        let x = reference.next_u64();
+        stream.extend((0..64).rev().map(|i| (x >> i) & 1));
+    }
+
+    let mut pos = 0;
+    for k in (0..=64).chain((0..=64).rev()) {
+        let expected = stream[pos..pos + k as usize].iter().fold(0, |acc, &b| (acc << 1) | b);
+        assert_eq!(rng.next_bits(k), expected, "k = {}", k);
+        pos += k as usize;
+    }
+    assert_eq!(rng.buffered() as usize, (64 - pos % 64) % 64);
+}
+
+#[test]
+fn bit_buffer_exact_outputs() {
+    // The first output of `XoroShiro128::new_unseeded()` is
+    // 12735464349504863268 = 0xb0bd_7559_b9e3_7c24.
+    let mut rng = BitBuffer::new(XoroShiro128::new_unseeded());
+    assert!(rng.next_bool());
+    assert!(!rng.next_bool());
+    assert_eq!(rng.next_bits(2), 0b11);
+    assert_eq!(rng.next_bits(12), 0x0bd);
+    assert_eq!(rng.next_u32(), 0x7559b9e3);
+    assert_eq!(rng.buffered(), 16);
+    assert_eq!(rng.next_bits(16), 0x7c24);
+    assert_eq!(rng.buffered(), 0);
+}
+
+#[test]
+fn bit_buffer_fewer_calls() {
+    let mut reference = XoroShiro128::from_seed_u64(2);
+    let mut rng = BitBuffer::new(reference.clone());
+    for _ in 0..64 {
+        rng.next_bool();
+    }
+    reference.next_u64();
+    let x = reference.next_u64();
+    assert_eq!(rng.next_u32(), (x >> 32) as u32);
+    assert_eq!(rng.next_u32(), x as u32);
+    // Crossing an output boundary takes the remaining bits first.
+    let y = reference.next_u64();
+    assert_eq!(rng.next_bits(60), y >> 4);
+    let z = reference.next_u64();
+    assert_eq!(rng.next_bits(8), ((y & 0xf) << 4) | (z >> 60));
+    assert_eq!(rng.into_inner().next_u64(), reference.next_u64());
+
+    let mut reference = SplitMix64::from_seed_u64(2);
+    let mut rng = BitBuffer::new(reference.clone());
+    assert_eq!(rng.next_u64(), reference.next_u64());
+    assert_eq!(rng.get_ref().state(), reference.state());
+}
+
+#[test]
+fn bit_buffer_bool() {
+    let mut rng = BitBuffer::new(XoroShiro128::from_seed_u64(1234));
+    let trues = (0..1_000_000).filter(|_| rng.next_bool()).count();
+    assert!((499_000..501_000).contains(&trues));
+}
+
+#[test]
+#[should_panic]
+fn bit_buffer_too_many_bits() {
+    BitBuffer::new(XoroShiro128::from_seed_u64(3)).next_bits(65);
+}
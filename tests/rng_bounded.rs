@@ -0,0 +1,102 @@
+#![allow(unknown_lints)]
+#![allow(clippy::unreadable_literal)]
+
+extern crate rand;
+extern crate xoroshiro;
+
+mod common;
+
+use common::splitmix64_yielding2;
+use rand::RngCore;
+use xoroshiro::rng::{SplitMix64, XoroShiro128};
+
+#[test]
+fn bounded_exact_outputs() {
+    // The outputs of `XoroShiro128::new_unseeded()` are 12735464349504863268,
+    // 8802522064177280339, 1639663216282820177, 11626439506680199927 and
+    // 3863091844355724613.
+    let mut rng = XoroShiro128::new_unseeded();
+    assert_eq!(rng.gen_below_u32(6), 4);
+    assert_eq!(rng.gen_below_u32(1000), 477);
+    assert_eq!(rng.gen_below_u32(0x80000001), 1801619611);
+    assert_eq!(rng.gen_below_u32(u32::MAX), 724624135);
+
+    let mut rng = XoroShiro128::new_unseeded();
+    assert_eq!(rng.gen_below_u64(6), 4);
+    assert_eq!(rng.gen_below_u64(1_000_000_000_000_000_000), 477185677266629711);
+    assert_eq!(rng.gen_below_u64(0x8000000000000001), 819831608141410088);
+    assert_eq!(rng.gen_below_u64(u64::MAX), 11626439506680199926);
+
+    let mut rng = XoroShiro128::new_unseeded();
+    assert_eq!(rng.gen_below_u128(6), 4);
+    assert_eq!(rng.gen_below_u128(1_000_000_000_000_000_000_000_000_000_000),
+               88886321061920158232819770970);
+    assert_eq!(rng.gen_below_u128((1 << 127) + 1), 28705319406079890716913485362003348402);
+
+    let mut rng = XoroShiro128::new_unseeded();
+    assert_eq!(rng.gen_range_i64(-10, 10), 3);
+    assert_eq!(rng.gen_range_i64(i64::MIN, i64::MAX), -420849972677495470);
+    assert_eq!(rng.gen_range_i64(i64::MIN, i64::MIN + 3), i64::MIN);
+}
+
+#[test]
+fn bounded_rejection() {
+    // For n = 3, the output 0 is one of the 2^64 mod 3 = 1 biased values, so it
+    // is rejected and the next output is used.
+    let mut rng = splitmix64_yielding2(0, u64::MAX);
+    assert_eq!(rng.gen_below_u64(3), 2);
+    // `SplitMix64::next_u32` uses the lower 32 bits.
+    let mut rng = splitmix64_yielding2(0, u64::MAX);
+    assert_eq!(rng.gen_below_u32(3), 2);
+    // Without rejection, the result only depends on the upper bits.
+    let mut rng = splitmix64_yielding2(1, 0);
+    assert_eq!(rng.gen_below_u64(3), 0);
+    assert_eq!(rng.next_u64() >> 12, 0);
+}
+
+#[test]
+fn bounded_single_value() {
+    let mut rng = XoroShiro128::from_seed_u64(1);
+    let mut reference = rng.clone();
+    assert_eq!(rng.gen_below_u32(1), 0);
+    assert_eq!(rng.gen_below_u64(1), 0);
+    assert_eq!(rng.gen_below_u128(1), 0);
+    assert_eq!(rng.gen_range_i64(-5, -4), -5);
+    // Each call consumes one output, or two for `u128`.
+    for _ in 0..5 {
+        reference.next_u64();
+    }
+    assert_eq!(rng.next_u64(), reference.next_u64());
+}
+
+#[test]
+fn bounded_distribution() {
+    let mut rng = XoroShiro128::from_seed_u64(2);
+    let mut counts = [0; 6];
+    for _ in 0..60_000 {
+        counts[rng.gen_below_u64(6) as usize] += 1;
+    }
+    for &count in &counts {
+        assert!((9_500..10_500).contains(&count));
+    }
+
+    let mut rng = SplitMix64::from_seed_u64(2);
+    for _ in 0..1000 {
+        let x = rng.gen_range_i64(-3, 4);
+        assert!((-3..4).contains(&x));
+        assert!(rng.gen_below_u32(7) < 7);
+        assert!(rng.gen_below_u128(7) < 7);
+    }
+}
+
+#[test]
+#[should_panic]
+fn bounded_empty() {
+    XoroShiro128::from_seed_u64(3).gen_below_u64(0);
+}
+
+#[test]
+#[should_panic]
+fn bounded_empty_range() {
+    XoroShiro128::from_seed_u64(3).gen_range_i64(5, 5);
+}
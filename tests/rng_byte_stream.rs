@@ -0,0 +1,156 @@
+#![allow(unknown_lints)]
+#![allow(clippy::unreadable_literal)]
+
+extern crate rand;
+extern crate xoroshiro;
+
+use rand::RngCore;
+use xoroshiro::rng::{Advance, ByteStream, SplitMix64, XoroShiro128};
+
+/// Return the first `n` bytes of the stream of `rng`.
+fn stream_bytes<R: RngCore + Clone>(rng: &R, n: usize) -> Vec<u8> {
+    let mut rng = rng.clone();
+    // Filling whole outputs matches the stream.
+    let mut bytes = vec![0; n.div_ceil(8) * 8];
+    rng.fill_bytes(&mut bytes);
+    bytes.truncate(n);
+    bytes
+}
+
+#[test]
+fn byte_stream_chunking() {
+    let rng = XoroShiro128::from_seed_u64(1);
+    let expected = stream_bytes(&rng, 1000);
+
+    for &sizes in &[&[3usize, 5][..], &[1, 1, 1, 13, 0, 7], &[8, 9, 10, 11, 12], &[17, 23, 64]] {
+        let mut stream = ByteStream::new(rng.clone());
+        let mut bytes = Vec::new();
+        for &size in sizes.iter().cycle().take(100) {
+            if bytes.len() + size > expected.len() {
+                break;
+            }
+            let mut chunk = vec![0; size];
+            stream.fill_bytes(&mut chunk);
+            bytes.extend(chunk);
+        }
+        assert_eq!(stream.position(), bytes.len() as u64);
+        assert_eq!(&bytes[..], &expected[..bytes.len()]);
+    }
+
+    // `fill_bytes` of the generator drops the rest of the last output.
+    let mut direct = rng.clone();
+    let mut x = [0; 8];
+    direct.fill_bytes(&mut x[..3]);
+    direct.fill_bytes(&mut x[3..]);
+    assert!(x[3..] != expected[3..8]);
+}
+
+#[test]
+fn byte_stream_words() {
+    let mut rng = XoroShiro128::from_seed_u64(2);
+    let expected = stream_bytes(&rng, 24);
+    let mut stream = ByteStream::new(rng.clone());
+
+    let mut first = [0; 1];
+    stream.fill_bytes(&mut first);
+    assert_eq!(first[0], expected[0]);
+    let x = stream.next_u32();
+    assert_eq!(x.to_le_bytes(), expected[1..5]);
+    let y = stream.next_u64();
+    assert_eq!(y.to_le_bytes(), expected[5..13]);
+    assert_eq!(stream.buffered(), 3);
+
+    // Without buffered bytes, this matches the generator.
+    let mut rest = [0; 3];
+    stream.fill_bytes(&mut rest);
+    rng.next_u64();
+    rng.next_u64();
+    assert_eq!(stream.next_u64(), rng.next_u64());
+    assert_eq!(stream.position(), 24);
+}
+
+#[test]
+fn byte_stream_seek() {
+    let rng = SplitMix64::from_seed_u64(3);
+    let expected = stream_bytes(&rng, 200);
+    let mut stream = ByteStream::new(rng.clone());
+
+    for &offset in &[0u64, 13, 8, 150, 7, 64, 199, 1] {
+        stream.seek(offset);
+        assert_eq!(stream.position(), offset);
+        let mut byte = [0; 1];
+        stream.fill_bytes(&mut byte);
+        assert_eq!(byte[0], expected[offset as usize], "offset = {}", offset);
+    }
+
+    // Seeking far ahead matches advancing the generator.
+    stream.seek(8 << 40);
+    let mut reference = rng.clone();
+    reference.advance(1 << 40);
+    assert_eq!(stream.next_u64(), reference.next_u64());
+    assert_eq!(stream.get_ref().state(), reference.state());
+}
+
+#[test]
+fn byte_stream_seek_wrapping() {
+    let rng = SplitMix64::from_seed_u64(4);
+    let expected = stream_bytes(&rng, 16);
+    let mut stream = ByteStream::new(rng);
+
+    // Reading across the end of the first 2^64 bytes wraps the position.
+    stream.seek(u64::MAX - 2);
+    let mut bytes = [0; 8];
+    stream.fill_bytes(&mut bytes);
+    assert_eq!(stream.position(), 5);
+    assert_eq!(stream.buffered(), 3);
+
+    // Seeking still counts from the creation of the stream.
+    stream.seek(3);
+    let mut bytes = [0; 8];
+    stream.fill_bytes(&mut bytes);
+    assert_eq!(&bytes[..], &expected[3..11]);
+}
+
+/// A generator counting its outputs, which can skip them.
+#[derive(Clone)]
+struct Counter(u64);
+
+impl RngCore for Counter {
+    fn next_u32(&mut self) -> u32 {
+        self.next_u64() as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0 += 1;
+        self.0
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        for chunk in dest.chunks_mut(8) {
+            let bytes = self.next_u64().to_le_bytes();
+            chunk.copy_from_slice(&bytes[..chunk.len()]);
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+impl Advance for Counter {
+    fn advance(&mut self, n: u64) {
+        self.0 = self.0.wrapping_add(n);
+    }
+}
+
+#[test]
+fn byte_stream_seek_advance() {
+    let mut stream = ByteStream::new(Counter(0));
+    stream.seek(8 * 41 + 2);
+    // The upper 6 bytes of output 42 are zero, then come 2 bytes of output 43.
+    assert_eq!(stream.next_u64(), 43 << 48);
+    stream.seek(8);
+    assert_eq!(stream.next_u64(), 2);
+    assert_eq!(stream.get_ref().0, 2);
+}
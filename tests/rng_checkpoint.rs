@@ -0,0 +1,98 @@
+#![allow(unknown_lints)]
+#![allow(clippy::unreadable_literal)]
+
+extern crate rand;
+extern crate xoroshiro;
+
+use rand::RngCore;
+use xoroshiro::rng::{
+    Algorithm, CheckpointError, StateError,
+    SplitMix64, XoroShiro128, XorShift1024,
+};
+
+#[test]
+fn xoroshiro128_checkpoint_format() {
+    let rng = XoroShiro128::new_unseeded();
+    let expected: Vec<u8> = vec![
+        88, 82, 78, 71, 1, 1, 16, 0,
+        105, 212, 167, 168, 84, 103, 58, 25,
+        187, 167, 59, 17, 5, 14, 131, 151,
+        164, 68, 20, 77,
+    ];
+    assert_eq!(rng.to_checkpoint_bytes(), expected);
+    assert_eq!(Algorithm::from_checkpoint_bytes(&expected),
+               Ok(Algorithm::XoroShiro128Plus));
+}
+
+#[test]
+fn checkpoint_roundtrip() {
+    let mut rng = SplitMix64::from_seed_u64(1477776061723855037);
+    rng.next_u64();
+    let mut restored = SplitMix64::from_checkpoint_bytes(&rng.to_checkpoint_bytes()).unwrap();
+    assert_eq!(restored.next_u64(), rng.next_u64());
+    let mut child = rng.split();
+    let mut restored = SplitMix64::from_checkpoint_bytes(&child.to_checkpoint_bytes()).unwrap();
+    assert_eq!(restored.gamma(), child.gamma());
+    assert_eq!(restored.next_u64(), child.next_u64());
+
+    let mut rng = XoroShiro128::from_seed_u64(1477776328140003287);
+    rng.next_u64();
+    let mut restored = XoroShiro128::from_checkpoint_bytes(&rng.to_checkpoint_bytes()).unwrap();
+    assert_eq!(restored.next_u64(), rng.next_u64());
+
+    let mut rng = XorShift1024::from_seed_u64(1477777179826044140);
+    for _ in 0..21 {
+        rng.next_u64();
+    }
+    let restored = XorShift1024::from_checkpoint_bytes(&rng.to_checkpoint_bytes()).unwrap();
+    assert_eq!(restored.state(), rng.state());
+}
+
+#[test]
+fn checkpoint_algorithm_mismatch() {
+    let bytes = XoroShiro128::new_unseeded().to_checkpoint_bytes();
+    assert_eq!(XorShift1024::from_checkpoint_bytes(&bytes).unwrap_err(),
+               CheckpointError::AlgorithmMismatch {
+                   expected: Algorithm::XorShift1024StarPhi,
+                   found: Algorithm::XoroShiro128Plus,
+               });
+    assert_eq!(Algorithm::from_checkpoint_bytes(&SplitMix64::new_unseeded().to_checkpoint_bytes()),
+               Ok(Algorithm::SplitMix64));
+}
+
+#[test]
+fn checkpoint_corrupted() {
+    let bytes = XoroShiro128::new_unseeded().to_checkpoint_bytes();
+
+    let mut corrupted = bytes.clone();
+    corrupted[10] ^= 1;
+    assert_eq!(XoroShiro128::from_checkpoint_bytes(&corrupted).unwrap_err(),
+               CheckpointError::ChecksumMismatch);
+
+    let mut corrupted = bytes.clone();
+    corrupted[0] = b'x';
+    assert_eq!(XoroShiro128::from_checkpoint_bytes(&corrupted).unwrap_err(),
+               CheckpointError::BadMagic);
+
+    let mut corrupted = bytes.clone();
+    corrupted[4] = 2;
+    assert_eq!(XoroShiro128::from_checkpoint_bytes(&corrupted).unwrap_err(),
+               CheckpointError::UnsupportedVersion(2));
+
+    assert_eq!(XoroShiro128::from_checkpoint_bytes(&bytes[..bytes.len() - 1]).unwrap_err(),
+               CheckpointError::InvalidLength);
+    assert_eq!(XoroShiro128::from_checkpoint_bytes(&[]).unwrap_err(),
+               CheckpointError::InvalidLength);
+}
+
+#[test]
+fn checkpoint_invalid_state() {
+    let zero: Vec<u8> = vec![
+        88, 82, 78, 71, 1, 1, 16, 0,
+        0, 0, 0, 0, 0, 0, 0, 0,
+        0, 0, 0, 0, 0, 0, 0, 0,
+        107, 76, 137, 193,
+    ];
+    assert_eq!(XoroShiro128::from_checkpoint_bytes(&zero).unwrap_err(),
+               CheckpointError::InvalidState(StateError::AllZero));
+}
@@ -0,0 +1,559 @@
+#![allow(unknown_lints)]
+#![allow(clippy::unreadable_literal)]
+
+extern crate rand;
+extern crate xoroshiro;
+
+use rand::RngCore;
+use xoroshiro::rng::{DotNetRandom, ErlangExrop, ErlangExsss, LuaRandom, SplittableRandom, StateError,
+                     V8MathRandom,
+                     Xoshiro256StarStar};
+
+#[test]
+fn v8_math_random() {
+    // Recorded with `node --random_seed=42` (V8 11.3), calling `Math.random()`
+    // 130 times, which refills the cache twice.
+    let expected: Vec<f64> = vec![
+        0.7939112874678715,
+        0.5254990606499601,
+        0.3518347850388237,
+        0.963056226312738,
+        0.5390526230404351,
+        0.4948165425338651,
+        0.77440109170183,
+        0.9447200639590003,
+        0.04764047070826849,
+        0.7635398202621702,
+        0.05871221435306562,
+        0.9232942015769332,
+        0.9423434820052736,
+        0.2977269074612272,
+        0.8785391291259448,
+        0.8657949499116644,
+        0.4171017815906195,
+        0.11696477726706211,
+        0.5858853919397093,
+        0.40413295453066245,
+        0.9009859655901196,
+        0.5674163180232696,
+        0.4834576901756673,
+        0.9423422942581774,
+        0.4569681873638456,
+        0.5208615067275075,
+        0.006235592162148196,
+        0.06077927875037803,
+        0.6479701604526202,
+        0.05355032154199124,
+        0.3478296927045208,
+        0.5648762901068196,
+        0.7493562057012213,
+        0.7039466628261539,
+        0.6736697738916553,
+        0.7503444963783306,
+        0.68220541010346,
+        0.260978123400158,
+        0.3725621341913594,
+        0.276257676580949,
+        0.9512270588229013,
+        0.18368907004545276,
+        0.25470657539289876,
+        0.6290112506623771,
+        0.6947402143425463,
+        0.8845673737516122,
+        0.09778729576237466,
+        0.8128602706937513,
+        0.39093717049964827,
+        0.21197635041544105,
+        0.26281596026025045,
+        0.4780664515589568,
+        0.0014865665071450707,
+        0.03210311485629469,
+        0.5378524669908111,
+        0.8787779069690327,
+        0.7783362483716729,
+        0.7738326646784492,
+        0.8148176454979934,
+        0.8212444506118473,
+        0.3891536450053332,
+        0.5226472860357483,
+        0.147200914094598,
+        0.08156904043271651,
+        0.4706713645501157,
+        0.2603201442309373,
+        0.5279993703358514,
+        0.08908663096488167,
+        0.2709988153163998,
+        0.1611673212249174,
+        0.2924511614188321,
+        0.10440581717956521,
+        0.31806933927979153,
+        0.29545521757331294,
+        0.7513643328992341,
+        0.8045080034351382,
+        0.6745477472477048,
+        0.4375197968227653,
+        0.029670459783009973,
+        0.1748664804563369,
+        0.1929813745579776,
+        0.30901817359137573,
+        0.04289918603834986,
+        0.07808772759207772,
+        0.4041954868001214,
+        0.6446211121529875,
+        0.05162165281180231,
+        0.29787121920513404,
+        0.4606863505213181,
+        0.9328905155402278,
+        0.455414645214004,
+        0.7167462321140694,
+        0.9336531371393875,
+        0.4230839257076895,
+        0.2953625660456829,
+        0.37004798343831835,
+        0.11367094900577923,
+        0.42158939331344936,
+        0.15204016123768938,
+        0.6095065851876302,
+        0.6839116913907792,
+        0.09363009263407474,
+        0.7820332575938305,
+        0.60278538130521,
+        0.5637209127659941,
+        0.7963338800994653,
+        0.9919468939708389,
+        0.21658897463710414,
+        0.21349572955951124,
+        0.2816679821383128,
+        0.9216825465257374,
+        0.06860514845229959,
+        0.5615393471126677,
+        0.6799981818858418,
+        0.14835465149340799,
+        0.9323641533299092,
+        0.21263611187678078,
+        0.23365448557990898,
+        0.6997670822829574,
+        0.25540957300456646,
+        0.9211446165885626,
+        0.8185405112911623,
+        0.30888276793315006,
+        0.7602817249584459,
+        0.8837863726306976,
+        0.841372247774842,
+        0.5410614907030067,
+        0.8220546914183824,
+        0.4620928683779777,
+        0.22660586183432097,
+    ];
+    let mut rng = V8MathRandom::from_seed_u64(42);
+    for (i, &e) in expected.iter().enumerate() {
+        assert_eq!(rng.next_f64(), e, "value {}", i);
+    }
+    assert_eq!(rng.cached(), 62);
+}
+
+#[test]
+fn v8_math_random_from_state() {
+    let mut a = V8MathRandom::from_seed_u64(7);
+    for _ in 0..64 {
+        a.next_f64();
+    }
+    assert_eq!(a.cached(), 0);
+    let mut b = V8MathRandom::from_state(a.state()).unwrap();
+    for _ in 0..100 {
+        assert_eq!(a.next_f64(), b.next_f64());
+    }
+    assert!(V8MathRandom::from_state([0, 0]).is_err());
+}
+
+// The Erlang vectors were computed with a transcription of `rand.erl` using
+// unbounded integers like Erlang, because no Erlang runtime was available to
+// record them.  The jumped states were computed
+// independently of the jump polynomials, by applying the 2^64-th power of
+// the transition matrix over GF(2).  They correspond to
+// `rand:seed_s(Alg, {1, 2, 3})` followed by `rand:uniform_s/1` five times and
+// `rand:uniform_s/2` eight times each with 6, 1000000 and 2^57 + 1, and to
+// `rand:jump/1` of the seeded state followed by `rand:uniform_s(100, _)`.
+
+#[test]
+fn erlang_exrop_vectors() {
+    let mut rng = ErlangExrop::from_seed_tuple(1, 2, 3);
+    assert_eq!(rng.state(), [0x2ffe4dece000af6, 0x3ffee70000e15ff]);
+    let mut jumped = rng.clone();
+
+    let floats = [0.7498295129076106, 0.06161655489244533, 0.7924073127680873,
+                  0.15526988640515305, 0.04841472033965044];
+    for &f in &floats {
+        assert_eq!(rng.uniform(), f);
+    }
+    for &n in &[2, 6, 3, 3, 5, 4, 6, 2] {
+        assert_eq!(rng.uniform_n(6), n);
+    }
+    for &n in &[38481, 427809, 671905, 446845, 984267, 797396, 976181, 394867] {
+        assert_eq!(rng.uniform_n(1000000), n);
+    }
+    let large = [124013598245119342, 102394715415916170, 13827448779918505, 99840017557407761,
+                 81778960286861709, 33769586255910611, 16267424874977392, 134972766814862375];
+    for &n in &large {
+        assert_eq!(rng.uniform_n((1 << 57) + 1), n);
+    }
+    assert_eq!(rng.state(), [0x170b6be474a220c, 0xafdca33cf58604]);
+
+    jumped.jump();
+    assert_eq!(jumped.state(), [0x17b03a56e493ac0, 0x19b3ba4cb8d0068]);
+    for &n in &[93, 45, 75, 12, 14] {
+        assert_eq!(jumped.uniform_n(100), n);
+    }
+
+    // Negative seeds are masked like Erlang's bignums.
+    let rng = ErlangExrop::from_seed_tuple(-1i64 as u64, -2i64 as u64, -3i64 as u64);
+    assert_eq!(rng.state(), [0x1001bd134fff51b, 0x11a7fff18a00]);
+}
+
+#[test]
+fn erlang_exsss_vectors() {
+    let mut rng = ErlangExsss::from_seed_tuple(1, 2, 3);
+    assert_eq!(rng.state(), [0x19ff867dbf682c9, 0x2c45d188009454f]);
+    let mut jumped = rng.clone();
+
+    let floats = [0.5455598952593053, 0.6039309974353404, 0.6684893034823949,
+                  0.22520431389287532, 0.5663557355891508];
+    for &f in &floats {
+        assert_eq!(rng.uniform(), f);
+    }
+    for &n in &[6, 5, 4, 3, 1, 3, 6, 1] {
+        assert_eq!(rng.uniform_n(6), n);
+    }
+    for &n in &[662909, 276874, 646942, 671577, 676619, 192044, 495645, 360222] {
+        assert_eq!(rng.uniform_n(1000000), n);
+    }
+    let large = [59917039445615319, 95191428355091908, 97428553629315705, 107148819748308382,
+                 143224552930146593, 73837174096459202, 123312309479488489, 40151581561894647];
+    for &n in &large {
+        assert_eq!(rng.uniform_n((1 << 57) + 1), n);
+    }
+    assert_eq!(rng.state(), [0x7033d9d9e473ad, 0x31883ac7809f65a]);
+
+    jumped.jump();
+    assert_eq!(jumped.state(), [0x7f1092dddc81dc, 0x3250aae82643184]);
+    for &n in &[40, 57, 85, 85, 60] {
+        assert_eq!(jumped.uniform_n(100), n);
+    }
+
+    let rng = ErlangExsss::from_seed_tuple(-1i64 as u64, -2i64 as u64, -3i64 as u64);
+    assert_eq!(rng.state(), [0xd055fcf2cbbd7b, 0x35835de1c9756ce]);
+}
+
+#[test]
+fn erlang_exrop() {
+    let mut rng = ErlangExrop::from_seed_tuple(1, 2, 3);
+    for _ in 0..1000 {
+        assert!(rng.next_u58() < 1 << 58);
+        let f = rng.uniform();
+        assert!((0. ..1.).contains(&f));
+        let n = rng.uniform_n(6);
+        assert!((1..=6).contains(&n));
+    }
+    assert!(rng.uniform_n(1 << 58) <= 1 << 58);
+    assert_eq!(rng.uniform_n(1), 1);
+
+    let state = rng.state();
+    assert!(state[0] < 1 << 58 && state[1] < 1 << 58);
+    let mut copy = ErlangExrop::from_state(state).unwrap();
+    assert_eq!(rng.next_u58(), copy.next_u58());
+}
+
+#[test]
+fn erlang_exsss() {
+    let mut rng = ErlangExsss::from_seed_tuple(1, 2, 3);
+    for _ in 0..1000 {
+        assert!(rng.next_u58() < 1 << 58);
+        let f = rng.uniform();
+        assert!((0. ..1.).contains(&f));
+        let n = rng.uniform_n(6);
+        assert!((1..=6).contains(&n));
+    }
+    assert!(rng.uniform_n(1 << 58) <= 1 << 58);
+    assert_eq!(rng.uniform_n(1), 1);
+
+    // Distinct seeds give distinct states, including the zero seed.
+    assert!(ErlangExsss::from_seed_tuple(0, 0, 0).state()
+        != ErlangExsss::from_seed_tuple(0, 0, 1).state());
+    let state = rng.state();
+    let mut copy = ErlangExsss::from_state(state).unwrap();
+    assert_eq!(rng.next_u58(), copy.next_u58());
+}
+
+#[test]
+fn erlang_from_state() {
+    assert_eq!(ErlangExrop::from_state([0, 0]).unwrap_err(), StateError::AllZero);
+    assert_eq!(ErlangExrop::from_state([1, 1 << 58]).unwrap_err(),
+               StateError::WordOutOfRange(1));
+    assert_eq!(ErlangExsss::from_state([0, 0]).unwrap_err(), StateError::AllZero);
+    assert_eq!(ErlangExsss::from_state([1 << 63, 1]).unwrap_err(),
+               StateError::WordOutOfRange(0));
+}
+
+#[test]
+fn erlang_jump() {
+    // Jumping commutes with stepping.
+    let mut a = ErlangExrop::from_seed_tuple(4, 5, 6);
+    let mut b = a.clone();
+    a.jump();
+    for _ in 0..10 {
+        b.next_u58();
+    }
+    b.jump();
+    for _ in 0..10 {
+        a.next_u58();
+    }
+    assert_eq!(a.state(), b.state());
+    assert!(a.state()[0] < 1 << 58 && a.state()[1] < 1 << 58);
+
+    let mut a = ErlangExsss::from_seed_tuple(4, 5, 6);
+    let mut b = a.clone();
+    a.jump();
+    for _ in 0..10 {
+        b.next_u58();
+    }
+    b.jump();
+    for _ in 0..10 {
+        a.next_u58();
+    }
+    assert_eq!(a.state(), b.state());
+    assert!(a.state()[0] < 1 << 58 && a.state()[1] < 1 << 58);
+}
+
+// The Lua and .NET conventions are checked against the xoshiro256** stream
+// they are built on, and against vectors computed with C transcriptions of
+// `lmathlib.c` (Lua 5.4.0 to 5.4.6) and of `XoshiroImpl` in
+// `Random.Xoshiro256StarStarImpl.cs` (.NET 6), because neither runtime was
+// available to record them.
+
+#[test]
+fn lua_random() {
+    let mut reference = Xoshiro256StarStar::from_state([42, 0xff, 7, 0]).unwrap();
+    for _ in 0..16 {
+        reference.next_u64();
+    }
+    let mut rng = LuaRandom::from_seed(42, 7);
+    assert_eq!(rng.state(), reference.state());
+
+    assert_eq!(rng.next_i64(), reference.next_u64() as i64);
+    assert_eq!(rng.next_f64(), (reference.next_u64() >> 11) as f64 / (1u64 << 53) as f64);
+    // Ranges whose size is a power of two use the lower bits.
+    assert_eq!(rng.next_i64_up_to(8), (reference.next_u64() & 7) as i64 + 1);
+    assert_eq!(rng.next_i64_range(i64::MIN, i64::MAX),
+               (reference.next_u64() as i64).wrapping_add(i64::MIN));
+    // Other ranges mask to the smallest sufficient number of bits and reject
+    // values that are out of range.
+    for _ in 0..1000 {
+        let expected = loop {
+            let x = reference.next_u64() & 7;
+            if x <= 6 {
+                break x as i64 - 3;
+            }
+        };
+        assert_eq!(rng.next_i64_range(-3, 3), expected);
+    }
+    assert_eq!(rng.next_i64_up_to(1), 1);
+}
+
+#[test]
+#[should_panic]
+fn lua_random_empty_interval() {
+    LuaRandom::from_seed(0, 0).next_i64_range(1, 0);
+}
+
+#[test]
+fn dotnet_random() {
+    let state = [1, 2, 3, 4];
+    let mut reference = Xoshiro256StarStar::from_state(state).unwrap();
+    let mut rng = DotNetRandom::from_state(state).unwrap();
+
+    assert_eq!(rng.next_u64(), reference.next_u64());
+    assert_eq!(rng.next_i32() as u64, reference.next_u64() >> 33);
+    assert_eq!(rng.next_i64() as u64, reference.next_u64() >> 1);
+    assert_eq!(rng.next_f64(), (reference.next_u64() >> 11) as f64 / (1u64 << 53) as f64);
+    assert_eq!(rng.next_f32(), (reference.next_u64() >> 40) as f32 / (1u32 << 24) as f32);
+    // Ranges whose size is a power of two use the upper bits.
+    assert_eq!(rng.next_i32_below(8) as u64, reference.next_u64() >> 61);
+    assert_eq!(rng.next_i32_range(-4, 4) as i64, (reference.next_u64() >> 61) as i64 - 4);
+    assert_eq!(rng.next_i64_below(1 << 40) as u64, reference.next_u64() >> 24);
+    assert_eq!(rng.next_i64_range(i64::MIN, i64::MAX) as u64,
+               (reference.next_u64() as i64).wrapping_add(i64::MIN) as u64);
+    // Empty and single element ranges do not advance the generator.
+    assert_eq!(rng.next_i32_below(1), 0);
+    assert_eq!(rng.next_i64_range(5, 5), 5);
+    assert_eq!(rng.state(), reference.state());
+
+    let mut bytes = [0; 11];
+    rng.fill_bytes(&mut bytes);
+    let a = reference.next_u64().to_le_bytes();
+    let b = reference.next_u64().to_le_bytes();
+    assert_eq!(&bytes[..8], &a);
+    assert_eq!(&bytes[8..], &b[..3]);
+
+    for _ in 0..1000 {
+        let x = rng.next_i32_range(-3, 4);
+        assert!((-3..4).contains(&x));
+        let x = rng.next_i64_below(1000);
+        assert!((0..1000).contains(&x));
+    }
+    assert!(DotNetRandom::from_state([0; 4]).is_err());
+}
+
+#[test]
+fn lua_random_vectors() {
+    // `math.randomseed(42)`, then `math.random()` four times, `math.random(0)`
+    // twice, `math.random(6)` ten times, `math.random(8)` six times,
+    // `math.random(-3, 3)` ten times, `math.random(1000000)` four times and
+    // `math.random(math.mininteger, math.maxinteger)` twice.
+    let mut rng = LuaRandom::from_seed(42, 0);
+    assert_eq!(rng.state(),
+               [0xb14c879519a69c8, 0x9887bf025aa3d299, 0xb996549176dd7d94, 0x94996c29e908897b]);
+    for &e in &[0.9308121780395682, 0.4517838993592431, 0.546883112434215,
+                0.7935893526382726] {
+        assert_eq!(rng.next_f64(), e);
+    }
+    for &e in &[-7059243630996204619, 177101407732369983] {
+        assert_eq!(rng.next_i64(), e);
+    }
+    for &e in &[2, 1, 3, 1, 1, 6, 1, 6, 2, 1] {
+        assert_eq!(rng.next_i64_up_to(6), e);
+    }
+    for &e in &[5, 6, 1, 4, 3, 6] {
+        assert_eq!(rng.next_i64_up_to(8), e);
+    }
+    for &e in &[1, -3, -3, 1, -3, -3, -2, 1, 2, 2] {
+        assert_eq!(rng.next_i64_range(-3, 3), e);
+    }
+    for &e in &[854784, 946664, 901289, 587839] {
+        assert_eq!(rng.next_i64_up_to(1000000), e);
+    }
+    for &e in &[5050846491257675227, 2281418195065078310] {
+        assert_eq!(rng.next_i64_range(i64::MIN, i64::MAX), e);
+    }
+    assert_eq!(rng.state(),
+               [0x3917d7f2c50562c6, 0xfddfdfb3bb99ab8c, 0x7e4b64e4c4f67e0, 0x1e739fce6ea69eb]);
+
+    // `math.randomseed(-7, 123)`, then `math.random()`.
+    assert_eq!(LuaRandom::from_seed(-7, 123).next_f64(), 0.19062321350489553);
+}
+
+#[test]
+fn dotnet_random_vectors() {
+    let state = [0x0123456789abcdef, 0xfedcba9876543210, 0x0f1e2d3c4b5a6978, 0x8796a5b4c3d2e1f0];
+    let mut rng = DotNetRandom::from_state(state).unwrap();
+    for &e in &[858993459, 1820531120, 416049609] {
+        assert_eq!(rng.next_i32(), e);
+    }
+    for &e in &[2, 2, 2, 2, 1, 2, 2, 1, 5, 5] {
+        assert_eq!(rng.next_i32_below(6), e);
+    }
+    for &e in &[-2, -2, 3, -3, 2, -5, 2, 0, -3, 2] {
+        assert_eq!(rng.next_i32_range(-5, 5), e);
+    }
+    for &e in &[-1409942353, 2057291093] {
+        assert_eq!(rng.next_i32_range(i32::MIN, i32::MAX), e);
+    }
+    for &e in &[8194639074776526204, 730497863368388996] {
+        assert_eq!(rng.next_i64(), e);
+    }
+    for &e in &[681987673161, 578548559264, 245697163149] {
+        assert_eq!(rng.next_i64_below(1000000000000), e);
+    }
+    for &e in &[8457932515648354986, -5779539719767498563] {
+        assert_eq!(rng.next_i64_range(i64::MIN, i64::MAX), e);
+    }
+    for &e in &[0.7848925187838514, 0.7482874542315366, 0.677482002796206] {
+        assert_eq!(rng.next_f64(), e);
+    }
+    for &e in &[0.14828783f32, 0.6733682, 0.035985112] {
+        assert_eq!(rng.next_f32(), e);
+    }
+    let mut bytes = [0; 11];
+    rng.fill_bytes(&mut bytes);
+    assert_eq!(bytes, [70, 42, 32, 39, 125, 250, 34, 239, 74, 232, 73]);
+    assert_eq!(rng.state(),
+               [0xe9c5be8b64ffb8f7, 0x1d7bb09f07f5815f, 0xfb9f95c9e6d676b7, 0x657ae09db93b4614]);
+}
+
+#[test]
+fn java_splittable_random() {
+    // Recorded with `new SplittableRandom(42)` on OpenJDK 17.
+    let mut rng = SplittableRandom::from_seed(42);
+    for &e in &[-4767286540954276203, 2949826092126892291, 5139283748462763858,
+                6349198060258255764, 701532786141963250] {
+        assert_eq!(rng.next_i64(), e);
+    }
+    for &e in &[-1746239153, 190917712, -1548930258, -1015872639, -1929329884] {
+        assert_eq!(rng.next_i32(), e);
+    }
+    for &e in &[45, 51, 71, 46, 28] {
+        assert_eq!(rng.next_i32_below(100), e);
+    }
+    for &e in &[3, 9, 2] {
+        assert_eq!(rng.next_i32_below(16), e);
+    }
+    for &e in &[3, -5, 3, -4, 1] {
+        assert_eq!(rng.next_i32_range(-5, 5), e);
+    }
+    for &e in &[779518842, -1932351312, -1884541041] {
+        assert_eq!(rng.next_i32_range(i32::MIN, i32::MAX), e);
+    }
+    for &e in &[788943567, 966192609, 34033480] {
+        assert_eq!(rng.next_i64_below(1000000007), e);
+    }
+    for &e in &[505, -750, -409, -954, -921] {
+        assert_eq!(rng.next_i64_range(-1000, 1000), e);
+    }
+    for &e in &[-6686406736592190891, 7010184598893129283, 1162605938390881553] {
+        assert_eq!(rng.next_i64_range(i64::MIN, i64::MAX), e);
+    }
+    for &e in &[0.2660528284133429, 0.7612051201486926, 0.09196696721367859,
+                0.5302541346816844, 0.1590549100202857] {
+        assert_eq!(rng.next_f64(), e);
+    }
+
+    let mut child = rng.split();
+    for &e in &[5130458325921273229, 7517674363587621567, 8360900503423388518] {
+        assert_eq!(child.next_i64(), e);
+    }
+    for &e in &[-6118883836101853133, 5928622861933973450, 1558413724744508586] {
+        assert_eq!(rng.next_i64(), e);
+    }
+    let mut grandchild = child.split();
+    for &e in &[-1099990420552176440, 7181619343645256840, 5046688362308243046] {
+        assert_eq!(grandchild.next_i64(), e);
+    }
+    assert_eq!(child.state()[1], 6697279520438405167);
+    assert_eq!(grandchild.state()[1], 7662223663794211015);
+}
+
+#[test]
+fn java_splittable_random_default_gen() {
+    // Recorded on OpenJDK 17 with `defaultGen` set to 12345 by reflection,
+    // creating two generators with `new SplittableRandom()`.
+    let mut gen = 12345;
+    let mut a = SplittableRandom::from_default_gen(&mut gen);
+    let mut b = SplittableRandom::from_default_gen(&mut gen);
+    assert_eq!(a.state()[1], 3576825416751071313);
+    assert_eq!(b.state()[1], 1535340248603250417);
+    for &e in &[4746188483488157281, 6479391515016189629, 2869842270791996350] {
+        assert_eq!(a.next_i64(), e);
+    }
+    for &e in &[5542613469306328503, -5367218721633079242, 4421019852440630253] {
+        assert_eq!(b.next_i64(), e);
+    }
+    assert_eq!(gen, 8709371129873703053);
+}
+
+#[test]
+fn java_splittable_random_from_state() {
+    let mut a = SplittableRandom::from_seed(7);
+    let mut b = a.split();
+    let mut c = SplittableRandom::from_state(b.state()).unwrap();
+    assert_eq!(b.next_i64(), c.next_i64());
+    assert_eq!(SplittableRandom::from_state([1, 2]).unwrap_err(), StateError::EvenGamma);
+}
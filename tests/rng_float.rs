@@ -0,0 +1,249 @@
+#![allow(unknown_lints)]
+#![allow(clippy::unreadable_literal)]
+
+extern crate rand;
+extern crate xoroshiro;
+
+mod common;
+
+use common::{splitmix64_yielding, splitmix64_yielding2};
+use rand::RngCore;
+use xoroshiro::rng::{AtomicSplitMix64, SplitMix64, XoroShiro128, Xoshiro128StarStar};
+
+#[test]
+fn float_exact_outputs() {
+    // The outputs of `XoroShiro128::new_unseeded()` are 12735464349504863268,
+    // 8802522064177280339, 1639663216282820177, 11626439506680199927 and
+    // 3863091844355724613.
+    let mut rng = XoroShiro128::new_unseeded();
+    assert_eq!(rng.next_f64(), 0.6903909057672432);
+    assert_eq!(rng.next_f64_open(), 0.47718567726662975);
+    assert_eq!(rng.next_f64_closed(), 0.08888632106192008);
+    assert_eq!(rng.next_f64_open_closed(), 0.6302705485706985);
+    assert_eq!(rng.next_f32(), 0.2094186);
+}
+
+#[test]
+fn float_upper_bits() {
+    let mut rng = XoroShiro128::from_seed_u64(1);
+    let mut reference = rng.clone();
+    for _ in 0..100 {
+        let x = reference.next_u64();
+        assert_eq!(rng.next_f64(), (x >> 11) as f64 / (1u64 << 53) as f64);
+        let x = reference.next_u64();
+        assert_eq!(rng.next_f32(), (x >> 40) as f32 / (1u32 << 24) as f32);
+    }
+
+    // Generators with 32-bit words use their 32-bit outputs for floats.
+    let mut rng = Xoshiro128StarStar::from_seed_u64(1);
+    let mut reference = rng.clone();
+    for _ in 0..100 {
+        let x = reference.next_u32();
+        assert_eq!(rng.next_f32(), (x >> 8) as f32 / (1u32 << 24) as f32);
+        let x = reference.next_u64();
+        assert_eq!(rng.next_f64(), (x >> 11) as f64 / (1u64 << 53) as f64);
+    }
+}
+
+#[test]
+fn float_bounds() {
+    let eps = 1. / (1u64 << 53) as f64;
+
+    assert_eq!(splitmix64_yielding(0).next_f64(), 0.);
+    assert_eq!(splitmix64_yielding(0).next_f64_open(), eps);
+    assert_eq!(splitmix64_yielding(0).next_f64_closed(), 0.);
+    assert_eq!(splitmix64_yielding(0).next_f64_open_closed(), eps);
+    assert_eq!(splitmix64_yielding(0).next_f32(), 0.);
+
+    assert_eq!(splitmix64_yielding(u64::MAX).next_f64(), 1. - eps);
+    assert_eq!(splitmix64_yielding(u64::MAX).next_f64_open(), 1. - eps);
+    assert_eq!(splitmix64_yielding(u64::MAX).next_f64_closed(), 1.);
+    assert_eq!(splitmix64_yielding(u64::MAX).next_f64_open_closed(), 1.);
+    assert_eq!(splitmix64_yielding(u64::MAX).next_f32(), 1. - 1. / (1u32 << 24) as f32);
+
+    // The lowest 11 bits are ignored.
+    assert_eq!(splitmix64_yielding(0x7ff).next_f64(), 0.);
+    assert_eq!(splitmix64_yielding(0x800).next_f64(), eps);
+
+    // Floats use the upper 24 bits of the 64-bit output.
+    assert_eq!(splitmix64_yielding(0xffff_ffff).next_f32(), 0.);
+    let eps32 = 1. / (1u32 << 24) as f32;
+    assert_eq!(splitmix64_yielding(0x8000_0100_0000_0000).next_f32(), 0.5 + eps32);
+}
+
+#[test]
+fn float_atomic_splitmix64() {
+    let mut rng = SplitMix64::from_seed_u64(5);
+    let atomic = AtomicSplitMix64::from(rng.clone());
+    assert_eq!(atomic.next_f64(), rng.next_f64());
+    assert_eq!(atomic.next_f32(), rng.next_f32());
+    assert_eq!(atomic.next_f64_open(), rng.next_f64_open());
+    assert_eq!(atomic.next_f64_closed(), rng.next_f64_closed());
+    assert_eq!(atomic.next_f64_open_closed(), rng.next_f64_open_closed());
+}
+
+#[test]
+fn float_full_precision_exact_outputs() {
+    // The first output has no leading zeros, so the result is in [0.5, 1) with
+    // the upper 52 bits of the second output as mantissa.
+    let mut rng = XoroShiro128::new_unseeded();
+    assert_eq!(rng.next_f64_full_precision(), 0.7385928386333148);
+
+    let mantissa = 0x123456789abcd;
+    let mut rng = splitmix64_yielding2(1 << 63, mantissa << 12);
+    assert_eq!(rng.next_f64_full_precision(), 0.5 + mantissa as f64 / (1u64 << 53) as f64);
+
+    // 63 leading zeros give the interval [2^-64, 2^-63), where `next_f64`
+    // could only return 0.
+    let mut rng = splitmix64_yielding2(1, mantissa << 12);
+    let x = rng.next_f64_full_precision();
+    assert_eq!(x.to_bits(), ((1023 - 64) << 52) | mantissa);
+
+    let mut rng = splitmix64_yielding2(u64::MAX, u64::MAX);
+    assert_eq!(rng.next_f64_full_precision(), 1. - 1. / (1u64 << 53) as f64);
+}
+
+#[test]
+fn float_full_precision_distribution() {
+    let mut rng = XoroShiro128::from_seed_u64(3);
+    let n = 100_000;
+    let mut upper_half = 0;
+    let mut second_quarter = 0;
+    let mut small = 0;
+    let mut small_odd = 0;
+    for _ in 0..n {
+        let x = rng.next_f64_full_precision();
+        assert!((0. ..1.).contains(&x));
+        if x >= 0.5 {
+            upper_half += 1;
+        } else if x >= 0.25 {
+            second_quarter += 1;
+        } else if x < 1. / 16. {
+            small += 1;
+            small_odd += x.to_bits() & 1;
+        }
+    }
+    assert!((49_000..51_000).contains(&upper_half));
+    assert!((24_000..26_000).contains(&second_quarter));
+    // The lowest mantissa bit of small values is still random.
+    assert!((small * 2 / 5..small * 3 / 5).contains(&small_odd));
+}
+
+#[test]
+fn float_uniform_range() {
+    let mut rng = XoroShiro128::from_seed_u64(4);
+    let ranges = [
+        (0., 1.), (1000., 1001.), (0.5, 3.), (1e-300, 1e-299), (0., 1e-310),
+        (-0., 5e-324), (0.5, 1e300), (1e-200, 1e200), (0., f64::MAX),
+    ];
+    for &(lo, hi) in &ranges {
+        for _ in 0..1000 {
+            let x = rng.uniform_f64(lo, hi);
+            assert!(x >= lo && x < hi, "{} not in [{}, {})", x, lo, hi);
+        }
+    }
+    assert_eq!(rng.uniform_f64(-0., 5e-324).to_bits(), 0);
+
+    // Below 2^-970, the spacing of the doubles is subnormal.
+    let lo = f64::from_bits((1023 - 1000) << 52);
+    let odd = (0..1000).map(|_| rng.uniform_f64(lo, 2. * lo).to_bits() & 1).sum::<u64>();
+    assert!((400..600).contains(&odd));
+
+    let mut rng = SplitMix64::from_seed_u64(4);
+    for _ in 0..1000 {
+        let x = rng.uniform_f64(1., 2.);
+        assert!((1. ..2.).contains(&x));
+    }
+}
+
+#[test]
+fn float_uniform_weights() {
+    // The doubles around 1 are 1 - 2^-52, 1 - 2^-53, 1 and 1 + 2^-52.  The
+    // two above 1 are twice as far apart, so they are twice as likely.
+    let eps = 1. / (1u64 << 52) as f64;
+    let lo = 1. - eps;
+    let hi = 1. + 2. * eps;
+    let mut rng = XoroShiro128::from_seed_u64(5);
+    let mut counts = [0; 4];
+    for _ in 0..60_000 {
+        let x = rng.uniform_f64(lo, hi);
+        let i = if x == lo { 0 } else if x == 1. - eps / 2. { 1 } else if x == 1. { 2 } else { 3 };
+        assert!(i != 3 || x == 1. + eps);
+        counts[i] += 1;
+    }
+    assert!((9_500..10_500).contains(&counts[0]));
+    assert!((9_500..10_500).contains(&counts[1]));
+    assert!((19_000..21_000).contains(&counts[2]));
+    assert!((19_000..21_000).contains(&counts[3]));
+}
+
+#[test]
+fn float_uniform_negative() {
+    let mut rng = XoroShiro128::from_seed_u64(6);
+    let ranges = [
+        (-1., 0.), (-1001., -1000.), (-3., -0.5), (-1e-299, -1e-300), (-1e-310, -0.),
+        (-f64::MAX, 0.), (-1e200, -1e-200),
+    ];
+    for &(lo, hi) in &ranges {
+        for _ in 0..1000 {
+            let x = rng.uniform_f64(lo, hi);
+            assert!(x >= lo && x < hi, "{} not in [{}, {})", x, lo, hi);
+        }
+    }
+    // The real number is rounded down, so the lower bound is reached.
+    assert_eq!(rng.uniform_f64(-5e-324, 0.), -5e-324);
+    let eps = 1. / (1u64 << 53) as f64;
+    assert_eq!(rng.uniform_f64(-1., -1. + eps), -1.);
+
+    // The doubles below -1 are twice as far apart as those above.
+    let mut counts = [0; 3];
+    for _ in 0..40_000 {
+        let x = rng.uniform_f64(-1. - 2. * eps, -1. + 2. * eps);
+        let i = if x == -1. - 2. * eps { 0 } else if x == -1. { 1 } else { 2 };
+        assert!(i != 2 || x == -1. + eps);
+        counts[i] += 1;
+    }
+    assert!((19_000..21_000).contains(&counts[0]));
+    assert!((9_500..10_500).contains(&counts[1]));
+    assert!((9_500..10_500).contains(&counts[2]));
+
+    let mut rng = SplitMix64::from_seed_u64(6);
+    for _ in 0..1000 {
+        let x = rng.uniform_f64(-2., -1.);
+        assert!((-2. ..-1.).contains(&x));
+    }
+}
+
+#[test]
+fn float_uniform_crossing_zero() {
+    let mut rng = XoroShiro128::from_seed_u64(7);
+    let ranges = [(-1., 1.), (-1., 3.), (-1e-300, 1e300), (-f64::MAX, f64::MAX), (-5e-324, 5e-324)];
+    for &(lo, hi) in &ranges {
+        for _ in 0..1000 {
+            let x = rng.uniform_f64(lo, hi);
+            assert!(x >= lo && x < hi, "{} not in [{}, {})", x, lo, hi);
+        }
+    }
+
+    // The sides are chosen proportionally to their lengths.
+    let negative = (0..40_000).filter(|_| rng.uniform_f64(-1., 3.) < 0.).count();
+    assert!((9_500..10_500).contains(&negative));
+    let negative = (0..40_000).filter(|_| rng.uniform_f64(-3., 1.) < 0.).count();
+    assert!((29_500..30_500).contains(&negative));
+
+    // Both doubles of the smallest range around zero are equally likely.
+    let zeros = (0..10_000).filter(|_| rng.uniform_f64(-5e-324, 5e-324) == 0.).count();
+    assert!((4_800..5_200).contains(&zeros));
+}
+
+#[test]
+#[should_panic]
+fn float_uniform_infinite() {
+    XoroShiro128::from_seed_u64(6).uniform_f64(f64::NEG_INFINITY, 0.);
+}
+
+#[test]
+#[should_panic]
+fn float_uniform_empty() {
+    XoroShiro128::from_seed_u64(6).uniform_f64(1., 1.);
+}
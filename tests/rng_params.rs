@@ -0,0 +1,67 @@
+#![allow(unknown_lints)]
+#![allow(clippy::unreadable_literal)]
+
+extern crate rand;
+extern crate xoroshiro;
+
+use rand::RngCore;
+use xoroshiro::rng::{
+    check_xoroshiro128_params, ParamsError, Plus, PlusPlus, Scrambled, XoroShiro128,
+    XoroShiro128Engine, XoroShiro128Params, XoroShiro128Params2016, XoroShiro128Params2018,
+    XoroShiro128ParamsCustom, XoroShiro128ParamsPlusPlus, XoroShiro128PlusPlus,
+};
+
+#[test]
+fn known_params_are_full_period() {
+    assert_eq!(check_xoroshiro128_params(55, 14, 36), Ok(()));
+    assert_eq!(check_xoroshiro128_params(24, 16, 37), Ok(()));
+    assert_eq!(check_xoroshiro128_params(49, 21, 28), Ok(()));
+}
+
+#[test]
+fn invalid_params_are_rejected() {
+    assert_eq!(check_xoroshiro128_params(0, 14, 36), Err(ParamsError::OutOfRange));
+    assert_eq!(check_xoroshiro128_params(55, 64, 36), Err(ParamsError::OutOfRange));
+    assert_eq!(check_xoroshiro128_params(1, 1, 1), Err(ParamsError::NotFullPeriod));
+    assert_eq!(check_xoroshiro128_params(55, 14, 35), Err(ParamsError::NotFullPeriod));
+}
+
+#[test]
+fn computed_jump_polynomials() {
+    type P2016 = XoroShiro128ParamsCustom<55, 14, 36>;
+    type P2018 = XoroShiro128ParamsCustom<24, 16, 37>;
+    type PPlusPlus = XoroShiro128ParamsCustom<49, 21, 28>;
+    assert_eq!(P2016::JUMP, XoroShiro128Params2016::JUMP);
+    assert_eq!(P2016::LONG_JUMP, XoroShiro128Params2016::LONG_JUMP);
+    assert_eq!(P2018::JUMP, XoroShiro128Params2018::JUMP);
+    assert_eq!(P2018::LONG_JUMP, XoroShiro128Params2018::LONG_JUMP);
+    assert_eq!(PPlusPlus::JUMP, XoroShiro128ParamsPlusPlus::JUMP);
+    assert_eq!(PPlusPlus::LONG_JUMP, XoroShiro128ParamsPlusPlus::LONG_JUMP);
+}
+
+#[test]
+fn custom_params_match_builtin() {
+    type Custom = Scrambled<XoroShiro128Engine<XoroShiro128ParamsCustom<55, 14, 36>>, Plus>;
+    let mut a = Custom::from_seed_u64(42);
+    let mut b = XoroShiro128::from_seed_u64(42);
+    for _ in 0..100 {
+        assert_eq!(a.next_u64(), b.next_u64());
+    }
+    a.jump();
+    b.jump();
+    a.long_jump();
+    b.long_jump();
+    for _ in 0..100 {
+        assert_eq!(a.next_u64(), b.next_u64());
+    }
+
+    type CustomPlusPlus =
+        Scrambled<XoroShiro128Engine<XoroShiro128ParamsCustom<49, 21, 28>>, PlusPlus>;
+    let mut a = CustomPlusPlus::from_seed_u64(7);
+    let mut b = XoroShiro128PlusPlus::from_seed_u64(7);
+    a.jump();
+    b.jump();
+    for _ in 0..100 {
+        assert_eq!(a.next_u64(), b.next_u64());
+    }
+}
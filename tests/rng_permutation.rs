@@ -0,0 +1,74 @@
+#![allow(unknown_lints)]
+#![allow(clippy::unreadable_literal)]
+
+extern crate xoroshiro;
+
+use xoroshiro::rng::RandomPermutation;
+
+/// Check that the permutation is a bijection of `0..n` with the given
+/// inverse.
+fn check_bijection(n: u64, seed: u64) {
+    let perm = RandomPermutation::new(n, seed);
+    assert_eq!(perm.len(), n);
+    let mut seen = vec![false; n as usize];
+    for (i, j) in perm.iter().enumerate() {
+        assert!(j < n);
+        assert!(!seen[j as usize], "n = {}: {} is visited twice", n, j);
+        seen[j as usize] = true;
+        assert_eq!(perm.get(i as u64), j);
+        assert_eq!(perm.inverse(j), i as u64);
+    }
+    assert!(seen.iter().all(|&s| s));
+}
+
+#[test]
+fn permutation_bijective() {
+    for n in 0..300 {
+        check_bijection(n, 0);
+        check_bijection(n, n * 31 + 7);
+    }
+    for &n in &[1023, 1024, 1025, 65535, 65536, 65537, 100000] {
+        check_bijection(n, 42);
+    }
+}
+
+#[test]
+fn permutation_empty() {
+    let perm = RandomPermutation::new(0, 1);
+    assert!(perm.is_empty());
+    assert_eq!(perm.iter().next(), None);
+}
+
+#[test]
+fn permutation_large() {
+    let n = 1_000_000_000_000;
+    let perm = RandomPermutation::new(n, 3);
+    for i in (0..1000).map(|i| i * 999_999_999).chain(vec![n - 1]) {
+        let j = perm.get(i);
+        assert!(j < n);
+        assert_eq!(perm.inverse(j), i);
+    }
+
+    let perm = RandomPermutation::new(u64::MAX, 3);
+    for i in (0..1000).map(|i| i << 50) {
+        assert_eq!(perm.inverse(perm.get(i)), i);
+    }
+}
+
+#[test]
+fn permutation_seed() {
+    let a: Vec<u64> = RandomPermutation::new(1000, 1).iter().collect();
+    let b: Vec<u64> = RandomPermutation::new(1000, 1).iter().collect();
+    let c: Vec<u64> = RandomPermutation::new(1000, 2).iter().collect();
+    assert_eq!(a, b);
+    assert_ne!(a, c);
+    // The permutation should not leave many elements in place.
+    let fixed = a.iter().enumerate().filter(|&(i, &j)| i as u64 == j).count();
+    assert!(fixed < 10);
+}
+
+#[test]
+#[should_panic]
+fn permutation_out_of_range() {
+    RandomPermutation::new(10, 0).get(10);
+}
@@ -0,0 +1,596 @@
+#![allow(unknown_lints)]
+#![allow(clippy::unreadable_literal)]
+
+extern crate rand;
+extern crate xoroshiro;
+
+use rand::RngCore;
+use xoroshiro::rng::{
+    XoroShiro128, XoroShiro128Star, XoroShiro128StarStar, XoroShiro128PlusPlus,
+    Xoshiro256Plus, Xoshiro256PlusPlus, Xoshiro256StarStar,
+    XoroShiro64Star, XoroShiro64StarStar, Xoshiro128StarStar, Xoshiro128PlusPlus,
+    Xoshiro512StarStar, Xoshiro512PlusPlus, XoroShiro1024StarStar, XoroShiro1024PlusPlus,
+    XorShift128Plus,
+};
+use xoroshiro::rng::{Product, Scrambled, XoroShiro128Engine, XoroShiro128Params2016};
+
+/// Compare 10 outputs, then 5 outputs after `jump` and 5 outputs after
+/// `long_jump` with the expected values.
+fn check<R: RngCore>(rng: &mut R, jump: fn(&mut R), long_jump: fn(&mut R), expected: &[u64]) {
+    assert_eq!(expected.len(), 20);
+    for &e in &expected[..10] {
+        assert_eq!(rng.next_u64(), e);
+    }
+    jump(rng);
+    for &e in &expected[10..15] {
+        assert_eq!(rng.next_u64(), e);
+    }
+    long_jump(rng);
+    for &e in &expected[15..] {
+        assert_eq!(rng.next_u64(), e);
+    }
+}
+
+/// Like `check`, but for generators with 32-bit outputs.
+fn check32<R: RngCore>(rng: &mut R, jump: fn(&mut R), long_jump: fn(&mut R), expected: &[u32]) {
+    assert_eq!(expected.len(), 20);
+    for &e in &expected[..10] {
+        assert_eq!(rng.next_u32(), e);
+    }
+    jump(rng);
+    for &e in &expected[10..15] {
+        assert_eq!(rng.next_u32(), e);
+    }
+    long_jump(rng);
+    for &e in &expected[15..] {
+        assert_eq!(rng.next_u32(), e);
+    }
+}
+
+#[test]
+fn xoroshiro128plus_2016() {
+    // Generated with the reference implementation, starting from the state
+    // below.
+    let expected: Vec<u64> = vec![
+        18446744073709551615,
+        17834414652373920280,
+        11728881392154729831,
+        8965824563484727225,
+        10582988499229659501,
+        4534619635670787247,
+        11884649833137210866,
+        16125076319631801022,
+        5380758569824330332,
+        3011810277608234938,
+        12310835473172691386,
+        4336724785419293543,
+        6317152671305349801,
+        4990168667818974649,
+        3130479205397813769,
+        14752350946945979784,
+        1476701855477355742,
+        12853660248088832550,
+        1536401492748225078,
+        4672827828256284601,
+    ];
+    let mut rng = XoroShiro128::from_state([0x0123456789abcdef, 0xfedcba9876543210]).unwrap();
+    check(&mut rng, XoroShiro128::jump, XoroShiro128::long_jump, &expected);
+}
+
+#[test]
+fn xoroshiro128star() {
+    // Generated with the reference implementation, starting from the state
+    // below.
+    let expected: Vec<u64> = vec![
+        742281298618748093,
+        228258071549409742,
+        14608122020458133418,
+        17641289253975093835,
+        11051622438434936765,
+        4919470311282845930,
+        16742532783141658597,
+        14493781370051776786,
+        14158559138201945189,
+        12438828593586035447,
+        13350922590121265675,
+        7638777885096676689,
+        2217491816262751826,
+        16874841579794803947,
+        9486558997840307373,
+        10747024027043870631,
+        13091764616864390386,
+        11641475270951914445,
+        6314135460099572727,
+        5934230279730481245,
+    ];
+    let mut rng = XoroShiro128Star::from_state([0x0123456789abcdef, 0xfedcba9876543210]).unwrap();
+    check(&mut rng, XoroShiro128Star::jump, XoroShiro128Star::long_jump, &expected);
+}
+
+#[test]
+fn xoroshiro128starstar() {
+    // Generated with the reference implementation, starting from the state
+    // below.
+    let expected: Vec<u64> = vec![
+        11068046444225724818,
+        11068046341419981074,
+        10201203040204483364,
+        11160412049735024024,
+        7263769401394201185,
+        8887263995814562641,
+        3302379277819244831,
+        8626678667246319936,
+        2432323081929534848,
+        10324599554430347070,
+        16451554986829946525,
+        5557845375630075,
+        3715456271317309409,
+        8932648728203930843,
+        8528510419364973243,
+        5116525268583697617,
+        12290674594518813406,
+        14961802323594050802,
+        3388088830415710059,
+        6663834930196755950,
+    ];
+    let mut rng = XoroShiro128StarStar::from_state([0x0123456789abcdef, 0xfedcba9876543210]).unwrap();
+    check(&mut rng, XoroShiro128StarStar::jump, XoroShiro128StarStar::long_jump, &expected);
+}
+
+#[test]
+fn xoroshiro128plusplus() {
+    // Generated with the reference implementation, starting from the state
+    // below.
+    let expected: Vec<u64> = vec![
+        81985529216486894,
+        11559359155456189540,
+        14742413462175304280,
+        11722321085874246307,
+        18195277883332905980,
+        4870808178002823119,
+        11508449876683880362,
+        3679945077129748252,
+        15245013254461901094,
+        8085817209879542815,
+        12237432625286861762,
+        3884418160272995298,
+        16178803949536828106,
+        6556531317468364923,
+        5055515889970917112,
+        7822070448868352779,
+        7503524959773712123,
+        6969880859564281318,
+        8355243459455937547,
+        14404881974603776539,
+    ];
+    let mut rng = XoroShiro128PlusPlus::from_state([0x0123456789abcdef, 0xfedcba9876543210]).unwrap();
+    check(&mut rng, XoroShiro128PlusPlus::jump, XoroShiro128PlusPlus::long_jump, &expected);
+}
+
+#[test]
+fn xoshiro256plus() {
+    // Generated with the reference implementation, starting from the state
+    // below.
+    let expected: Vec<u64> = vec![
+        5,
+        211106232532999,
+        211106635186183,
+        9223759065350669058,
+        9250833439874351877,
+        13862484359527728515,
+        2346507365006083650,
+        1168864526675804870,
+        34095955243042024,
+        3466914240207415127,
+        1225792699406591714,
+        16990647642536364915,
+        5389303176329026558,
+        15450559335115415613,
+        14281436825261249286,
+        141292599759985583,
+        5105718871857241733,
+        1202724441515045851,
+        13713253811183864823,
+        4247325598728540668,
+    ];
+    let mut rng = Xoshiro256Plus::from_state([1, 2, 3, 4]).unwrap();
+    check(&mut rng, Xoshiro256Plus::jump, Xoshiro256Plus::long_jump, &expected);
+}
+
+#[test]
+fn xoshiro256plusplus() {
+    // Generated with the reference implementation, starting from the state
+    // below.
+    let expected: Vec<u64> = vec![
+        41943041,
+        58720359,
+        3588806011781223,
+        3591011842654386,
+        9228616714210784205,
+        9973669472204895162,
+        14011001112246962877,
+        12406186145184390807,
+        15849039046786891736,
+        10450023813501588000,
+        8998556026673787160,
+        14860850672632069730,
+        6793339527323295379,
+        9551689320381348118,
+        6883119374208437951,
+        12689231958257011011,
+        17209624304624775433,
+        11401923538714308826,
+        16356363923438331861,
+        7563671753836579926,
+    ];
+    let mut rng = Xoshiro256PlusPlus::from_state([1, 2, 3, 4]).unwrap();
+    check(&mut rng, Xoshiro256PlusPlus::jump, Xoshiro256PlusPlus::long_jump, &expected);
+}
+
+#[test]
+fn xoshiro256starstar() {
+    // Generated with the reference implementation, starting from the state
+    // below.
+    let expected: Vec<u64> = vec![
+        11520,
+        0,
+        1509978240,
+        1215971899390074240,
+        1216172134540287360,
+        607988272756665600,
+        16172922978634559625,
+        8476171486693032832,
+        10595114339597558777,
+        2904607092377533576,
+        9810239295077353613,
+        13688229392200714283,
+        5138509750170400445,
+        8729095292319328146,
+        8374476504124740766,
+        18080801543426665271,
+        16498952636333066056,
+        12878724415460189093,
+        7376544198128099888,
+        8495268056904622114,
+    ];
+    let mut rng = Xoshiro256StarStar::from_state([1, 2, 3, 4]).unwrap();
+    check(&mut rng, Xoshiro256StarStar::jump, Xoshiro256StarStar::long_jump, &expected);
+}
+
+#[test]
+fn xoroshiro64star() {
+    // Generated with the reference implementation, starting from the state
+    // below.
+    let expected: Vec<u32> = vec![
+        4222771517,
+        3820027567,
+        3541213502,
+        3599703160,
+        2396410709,
+        2409444072,
+        2701652895,
+        3165136028,
+        3365752954,
+        940654441,
+    ];
+    let mut rng = XoroShiro64Star::from_state([0x01234567, 0x89abcdef]).unwrap();
+    for &e in &expected {
+        assert_eq!(rng.next_u32(), e);
+    }
+}
+
+#[test]
+fn xoroshiro64starstar() {
+    // Generated with the reference implementation, starting from the state
+    // below.
+    let expected: Vec<u32> = vec![
+        1333577403,
+        1319054828,
+        3953444674,
+        426888066,
+        1173624181,
+        3258962261,
+        2767733700,
+        3910590963,
+        1649560765,
+        180855235,
+    ];
+    let mut rng = XoroShiro64StarStar::from_state([0x01234567, 0x89abcdef]).unwrap();
+    for &e in &expected {
+        assert_eq!(rng.next_u32(), e);
+    }
+}
+
+#[test]
+fn xoshiro128starstar() {
+    // Generated with the reference implementation, starting from the state
+    // below.
+    let expected: Vec<u32> = vec![
+        11520,
+        0,
+        5927040,
+        70819200,
+        2031721883,
+        1637235492,
+        1287239034,
+        3734860849,
+        3729100597,
+        4258142804,
+        4290708443,
+        2568308862,
+        3110351530,
+        3156522848,
+        2216925636,
+        816734234,
+        2696025885,
+        2334093708,
+        3178225416,
+        3404027523,
+    ];
+    let mut rng = Xoshiro128StarStar::from_state([1, 2, 3, 4]).unwrap();
+    check32(&mut rng, Xoshiro128StarStar::jump, Xoshiro128StarStar::long_jump, &expected);
+}
+
+#[test]
+fn xoshiro128plusplus() {
+    // Generated with the reference implementation, starting from the state
+    // below.
+    let expected: Vec<u32> = vec![
+        641,
+        1573767,
+        3222811527,
+        3517856514,
+        836907274,
+        4247214768,
+        3867114732,
+        1355841295,
+        495546011,
+        621204420,
+        2449832661,
+        998991081,
+        2723162570,
+        3660473776,
+        3985610038,
+        2160889372,
+        3373150916,
+        97582452,
+        2477389457,
+        3151845782,
+    ];
+    let mut rng = Xoshiro128PlusPlus::from_state([1, 2, 3, 4]).unwrap();
+    check32(&mut rng, Xoshiro128PlusPlus::jump, Xoshiro128PlusPlus::long_jump, &expected);
+}
+
+#[test]
+fn next_u64_from_two_u32() {
+    let mut a = Xoshiro128StarStar::from_seed_u64(1);
+    let mut b = a.clone();
+    for _ in 0..10 {
+        let lo = u64::from(a.next_u32());
+        let hi = u64::from(a.next_u32());
+        assert_eq!(b.next_u64(), (hi << 32) | lo);
+    }
+
+    let mut a = XoroShiro64Star::from_seed_u64(1);
+    let mut b = a.clone();
+    let mut bytes = [0; 7];
+    b.fill_bytes(&mut bytes);
+    let x = a.next_u32().to_le_bytes();
+    let y = a.next_u32().to_le_bytes();
+    assert_eq!(bytes[..4], x);
+    assert_eq!(bytes[4..], y[..3]);
+    assert_eq!(a.next_u32(), b.next_u32());
+}
+
+#[test]
+fn state_of_32_bit_generators() {
+    let rng = Xoshiro128PlusPlus::from_state([1, 2, 3, 0xffffffff]).unwrap();
+    let s = rng.to_string();
+    assert_eq!(s, "xoshiro128++:00000001:00000002:00000003:ffffffff");
+    assert_eq!(s.parse::<Xoshiro128PlusPlus>().unwrap().state(), rng.state());
+    assert!("xoshiro128++:1:2:3:4".parse::<Xoshiro128PlusPlus>().is_err());
+
+    let rng = XoroShiro64StarStar::from_state([0x01234567, 0x89abcdef]).unwrap();
+    let bytes = rng.to_checkpoint_bytes();
+    // The two 32-bit words are stored as one little-endian 64-bit word.
+    assert_eq!(bytes[6..16], [8, 0, 0x67, 0x45, 0x23, 0x01, 0xef, 0xcd, 0xab, 0x89]);
+    let restored = XoroShiro64StarStar::from_checkpoint_bytes(&bytes).unwrap();
+    assert_eq!(restored.state(), rng.state());
+
+    // A 64-bit state can be seeded from a single 64-bit word.
+    let a = XoroShiro64Star::from_seed_u64s(&[1]).unwrap();
+    let b = XoroShiro64Star::from_seed_u64s(&[2]).unwrap();
+    assert!(a.state() != b.state());
+}
+
+#[test]
+#[should_panic]
+fn seed_larger_than_state() {
+    let _ = XoroShiro64Star::from_seed_u64s(&[1, 2]);
+}
+
+#[test]
+fn xoshiro512starstar() {
+    // Generated with the reference implementation, starting from the state
+    // below.
+    let expected: Vec<u64> = vec![
+        11520,
+        0,
+        23040,
+        23667840,
+        144955163520,
+        303992986974289920,
+        25332796375735680,
+        296904390158016,
+        13911081092387501979,
+        15304787717237593024,
+        3931668335945592750,
+        13215432723246274681,
+        3234764729326008921,
+        12414142756398100933,
+        18304927974264175341,
+        1018822940395763308,
+        11071559044585109092,
+        4986752858876159573,
+        851252076820004044,
+        10802368170143333544,
+    ];
+    let mut rng = Xoshiro512StarStar::from_state([1, 2, 3, 4, 5, 6, 7, 8]).unwrap();
+    check(&mut rng, Xoshiro512StarStar::jump, Xoshiro512StarStar::long_jump, &expected);
+}
+
+#[test]
+fn xoshiro512plusplus() {
+    // Generated with the reference implementation, starting from the state
+    // below.
+    let expected: Vec<u64> = vec![
+        524291,
+        1048578,
+        539099140,
+        3299073855497,
+        6917532603230064654,
+        7494048333530275843,
+        14418333309547923463,
+        10960079161595355914,
+        18279570946505382726,
+        10209173166699159237,
+        8535747895809688178,
+        368603605112641356,
+        15305804099011793627,
+        473856260160213257,
+        7683493470624043659,
+        9094884820164132444,
+        7430802098064011682,
+        888259204992097586,
+        14440964558717093198,
+        13994707952087936658,
+    ];
+    let mut rng = Xoshiro512PlusPlus::from_state([1, 2, 3, 4, 5, 6, 7, 8]).unwrap();
+    check(&mut rng, Xoshiro512PlusPlus::jump, Xoshiro512PlusPlus::long_jump, &expected);
+}
+
+const STATE_1024: [u64; 16] = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16];
+
+#[test]
+fn xoroshiro1024starstar() {
+    // Generated with the reference implementation, starting from the state
+    // below.
+    let expected: Vec<u64> = vec![
+        11520,
+        17280,
+        23040,
+        28800,
+        34560,
+        40320,
+        46080,
+        51840,
+        57600,
+        63360,
+        358910546792860258,
+        7731747835776876456,
+        12050254608135109616,
+        15118857474049918871,
+        15252404005694641786,
+        15534422189458286950,
+        13509598452212304438,
+        2056596672880629193,
+        8268273804750862430,
+        15473955096505349976,
+    ];
+    let mut rng = XoroShiro1024StarStar::from_state((STATE_1024, 0)).unwrap();
+    check(&mut rng, XoroShiro1024StarStar::jump, XoroShiro1024StarStar::long_jump, &expected);
+}
+
+#[test]
+fn xoroshiro1024plusplus() {
+    // Generated with the reference implementation, starting from the state
+    // below.
+    let expected: Vec<u64> = vec![
+        25165825,
+        1729382463093866496,
+        1729382469544706816,
+        2305896067134128920,
+        2882358539580539928,
+        3472347753827474720,
+        4049248931463568936,
+        8088996010033751856,
+        8778205716228747320,
+        10512250523491973488,
+        13720894327712191366,
+        1602181123719779762,
+        1258332071746178749,
+        7030239386883441702,
+        12613849773973994848,
+        6285629319593042543,
+        3863869850644267551,
+        5823513350143995320,
+        14921906466101343508,
+        4276896491542892160,
+    ];
+    let mut rng = XoroShiro1024PlusPlus::from_state((STATE_1024, 0)).unwrap();
+    check(&mut rng, XoroShiro1024PlusPlus::jump, XoroShiro1024PlusPlus::long_jump, &expected);
+}
+
+#[test]
+fn large_state_seeding() {
+    // Seeding from a `u64` fills the state with `SplitMix64`.
+    let rng = Xoshiro512StarStar::from_seed_u64(3);
+    let mut sm = xoroshiro::rng::SplitMix64::from_seed_u64(3);
+    let mut expected = [0; 8];
+    for w in expected.iter_mut() {
+        *w = sm.next_u64();
+    }
+    assert_eq!(rng.state(), expected);
+
+    let rng = XoroShiro1024PlusPlus::from_seed_u64s(&[1, 2, 3]).unwrap();
+    let (s, p) = rng.state();
+    assert_eq!(p, 0);
+    assert!(s.iter().all(|&x| x != 0));
+    let other = XoroShiro1024PlusPlus::from_seed_u64s(&[1, 2, 4]).unwrap();
+    assert!(other.state().0 != s);
+}
+
+#[test]
+fn xorshift128plus() {
+    // Generated with the original reference implementation, using the jump
+    // polynomial of `XorShift128Plus`.
+    let expected: Vec<u64> = vec![
+        5411125452199041287,
+        5411125573960050055,
+        1673347991207915948,
+        15367241571318302782,
+        5703527350725789642,
+        10556830953396946758,
+        6288127045848135249,
+        14641453009687487633,
+        5588955596320063348,
+        3025293434542471747,
+        7559282779706690034,
+        1665798937595134411,
+        2063082630474384962,
+        11984324785796231485,
+        6777233229182153434,
+    ];
+    let mut rng = XorShift128Plus::from_state([0x0123456789abcdef, 0xfedcba9876543210]).unwrap();
+    for &e in &expected[..10] {
+        assert_eq!(rng.next_u64(), e);
+    }
+    rng.jump();
+    for &e in &expected[10..] {
+        assert_eq!(rng.next_u64(), e);
+    }
+}
+
+#[test]
+fn xoroshiro128_product_2016() {
+    // The early xoroshiro128* variant, as previously used by the benchmarks.
+    let (mut s0, mut s1): (u64, u64) = (0x193a6754a8a7d469, 0x97830e05113ba7bb);
+    let mut rng = Scrambled::<XoroShiro128Engine<XoroShiro128Params2016>, Product>::new_unseeded();
+    for _ in 0..100 {
+        let r = s0.wrapping_mul(s1);
+        s1 ^= s0;
+        s0 = s0.rotate_left(55) ^ s1 ^ (s1 << 14);
+        s1 = s1.rotate_left(36);
+        assert_eq!(rng.next_u64(), r);
+    }
+}
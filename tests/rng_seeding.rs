@@ -1,11 +1,12 @@
 #![allow(unknown_lints)]
-#![allow(unreadable_literal)]
+#![allow(clippy::unreadable_literal)]
 
 extern crate rand;
 extern crate xoroshiro;
 
 use rand::{Rng, RngCore};
-use xoroshiro::rng::{XoroShiro128, XorShift1024};
+use rand::distributions::Standard;
+use xoroshiro::rng::{StateError, XoroShiro128, XorShift1024};
 
 #[test]
 fn xoroshiro128_sm64_seed() {
@@ -64,7 +65,7 @@ fn xoroshiro128_sm64_seed() {
     ];
 
     let mut rng = XoroShiro128::from_seed_u64(seed);
-    for (i, &j) in rng.gen_iter::<u64>().zip(expected.iter()) {
+    for (i, &j) in rng.sample_iter::<u64, _>(&Standard).zip(expected.iter()) {
         assert_eq!(i, j);
     }
 }
@@ -126,7 +127,7 @@ fn xorshift1024_sm64_seed() {
     ];
 
     let mut rng = XorShift1024::from_seed_u64(seed);
-    for (i, &j) in rng.gen_iter::<u64>().zip(expected.iter()) {
+    for (i, &j) in rng.sample_iter::<u64, _>(&Standard).zip(expected.iter()) {
         assert_eq!(i, j);
     }
 }
@@ -196,7 +197,7 @@ fn xoroshiro128_sm64_seed_jumps() {
     for _ in 0..10 {
         rng.jump();
     }
-    for (i, &j) in rng.gen_iter::<u64>().zip(expected.iter()) {
+    for (i, &j) in rng.sample_iter::<u64, _>(&Standard).zip(expected.iter()) {
         assert_eq!(i, j);
     }
 }
@@ -261,7 +262,79 @@ fn xorshift1024_sm64_seed_jumps() {
     for _ in 0..10 {
         rng.jump();
     }
-    for (i, &j) in rng.gen_iter::<u64>().zip(expected.iter()) {
+    for (i, &j) in rng.sample_iter::<u64, _>(&Standard).zip(expected.iter()) {
         assert_eq!(i, j);
     }
 }
+
+#[test]
+fn xoroshiro128_u128_seed() {
+    let seed: u128 = 0x0123456789abcdef_fedcba9876543210;
+    let expected: Vec<u64> = vec![
+        3196844229164923114,
+        16926042198659325081,
+        1347406503750560653,
+        1551813784963471036,
+        9366278617450229355,
+    ];
+
+    let mut rng = XoroShiro128::from_seed_u128(seed).unwrap();
+    for (i, &j) in rng.sample_iter::<u64, _>(&Standard).zip(expected.iter()) {
+        assert_eq!(i, j);
+    }
+
+    let mut rng1 = XoroShiro128::from_seed_u128(seed).unwrap();
+    let mut rng2 = XoroShiro128::from_seed_u64s(&[seed as u64, (seed >> 64) as u64]).unwrap();
+    for _ in 0..10 {
+        assert_eq!(rng1.next_u64(), rng2.next_u64());
+    }
+}
+
+#[test]
+fn xoroshiro128_u128_seed_distinct() {
+    // Seeds only differing in their upper 64 bits must not collide.
+    let mut rng1 = XoroShiro128::from_seed_u128(1).unwrap();
+    let mut rng2 = XoroShiro128::from_seed_u128(1 | 1 << 64).unwrap();
+    let mut rng3 = XoroShiro128::from_seed_u128(1 | 2 << 64).unwrap();
+    let x1 = rng1.next_u64();
+    let x2 = rng2.next_u64();
+    let x3 = rng3.next_u64();
+    assert!(x1 != x2 && x2 != x3 && x1 != x3);
+}
+
+#[test]
+fn xoroshiro128_u128_seed_zero_state() {
+    // The only seed mapped to the all zero state is rejected.
+    assert_eq!(XoroShiro128::from_seed_u128(0x61c8864680b583eb61c8864680b583eb).unwrap_err(),
+               StateError::AllZero);
+    assert!(XoroShiro128::from_seed_u128(0x61c8864680b583eb61c8864680b583ea).is_ok());
+}
+
+#[test]
+#[should_panic]
+fn xoroshiro128_u64s_seed_too_long() {
+    let _ = XoroShiro128::from_seed_u64s(&[1, 2, 3]);
+}
+
+#[test]
+fn xorshift1024_u128_seed() {
+    let seed: u128 = 0x0123456789abcdef_fedcba9876543210;
+    let expected: Vec<u64> = vec![
+        17511648501074273436,
+        7569363043478523234,
+        245491492446789554,
+        9081857963779454360,
+        10483563459819372375,
+    ];
+
+    let mut rng = XorShift1024::from_seed_u128(seed).unwrap();
+    for (i, &j) in rng.sample_iter::<u64, _>(&Standard).zip(expected.iter()) {
+        assert_eq!(i, j);
+    }
+
+    let mut rng1 = XorShift1024::from_seed_u64s(&[1, 2, 3]).unwrap();
+    let mut rng2 = XorShift1024::from_seed_u64s(&[1, 2, 4]).unwrap();
+    let x1: Vec<u64> = rng1.sample_iter(&Standard).take(16).collect();
+    let x2: Vec<u64> = rng2.sample_iter(&Standard).take(16).collect();
+    assert!(x1 != x2);
+}
@@ -0,0 +1,140 @@
+#![cfg(feature = "serde")]
+#![allow(unknown_lints)]
+#![allow(clippy::unreadable_literal)]
+
+extern crate bincode;
+extern crate rand;
+extern crate serde_json;
+extern crate xoroshiro;
+
+use rand::{RngCore, SeedableRng};
+use xoroshiro::rng::{SplitMix64, SplittableRandom, XoroShiro128, XorShift1024, XorShift1024Seed, Xoshiro512Seed,
+                     Xoshiro512StarStar};
+
+#[test]
+fn splitmix64_serde() {
+    let mut rng = SplitMix64::from_seed_u64(1477776061723855037);
+    rng.next_u64();
+
+    let json = serde_json::to_string(&rng).unwrap();
+    let mut rng1: SplitMix64 = serde_json::from_str(&json).unwrap();
+    let bin = bincode::serialize(&rng).unwrap();
+    let mut rng2: SplitMix64 = bincode::deserialize(&bin).unwrap();
+    for _ in 0..10 {
+        let x = rng.next_u64();
+        assert_eq!(rng1.next_u64(), x);
+        assert_eq!(rng2.next_u64(), x);
+    }
+
+    let child = rng.split();
+    let json = serde_json::to_string(&child).unwrap();
+    let rng1: SplitMix64 = serde_json::from_str(&json).unwrap();
+    assert_eq!(rng1.gamma(), child.gamma());
+    // The format without the gamma is still accepted.
+    let rng2: SplitMix64 = serde_json::from_str(r#"{"x":5}"#).unwrap();
+    assert_eq!(rng2.gamma(), SplitMix64::from_state(5).gamma());
+    assert!(serde_json::from_str::<SplitMix64>(r#"{"x":5,"gamma":2}"#).is_err());
+}
+
+#[test]
+fn splittable_random_serde() {
+    let mut rng = SplittableRandom::from_seed(42);
+    let rng = rng.split();
+    let json = serde_json::to_string(&rng).unwrap();
+    let rng1: SplittableRandom = serde_json::from_str(&json).unwrap();
+    let bin = bincode::serialize(&rng).unwrap();
+    let rng2: SplittableRandom = bincode::deserialize(&bin).unwrap();
+    assert_eq!(rng1.state(), rng.state());
+    assert_eq!(rng2.state(), rng.state());
+
+    assert!(serde_json::from_str::<SplittableRandom>(r#"{"seed":1,"gamma":2}"#).is_err());
+}
+
+#[test]
+fn xoroshiro128_serde() {
+    let rng = XoroShiro128::new_unseeded();
+    let json = serde_json::to_string(&rng).unwrap();
+    assert_eq!(json, r#"{"s0":1817879012901901417,"s1":10917585336602961851}"#);
+
+    let mut rng = XoroShiro128::from_seed_u64(1477776328140003287);
+    rng.next_u64();
+    let json = serde_json::to_string(&rng).unwrap();
+    let rng1: XoroShiro128 = serde_json::from_str(&json).unwrap();
+    let bin = bincode::serialize(&rng).unwrap();
+    assert_eq!(bin.len(), 16);
+    let rng2: XoroShiro128 = bincode::deserialize(&bin).unwrap();
+    assert_eq!(rng1.state(), rng.state());
+    assert_eq!(rng2.state(), rng.state());
+}
+
+#[test]
+fn xoroshiro128_serde_invalid() {
+    assert!(serde_json::from_str::<XoroShiro128>(r#"{"s0":0,"s1":0}"#).is_err());
+    assert!(bincode::deserialize::<XoroShiro128>(&[0; 16]).is_err());
+}
+
+#[test]
+fn xorshift1024_serde() {
+    let mut rng = XorShift1024::from_seed_u64(1477777179826044140);
+    for _ in 0..21 {
+        rng.next_u64();
+    }
+    let json = serde_json::to_string(&rng).unwrap();
+    let rng1: XorShift1024 = serde_json::from_str(&json).unwrap();
+    let bin = bincode::serialize(&rng).unwrap();
+    let rng2: XorShift1024 = bincode::deserialize(&bin).unwrap();
+    assert_eq!(rng1.state(), rng.state());
+    assert_eq!(rng2.state(), rng.state());
+}
+
+#[test]
+fn xorshift1024_serde_invalid() {
+    let zero = format!(r#"{{"s":{:?},"p":0}}"#, [0u64; 16]);
+    assert!(serde_json::from_str::<XorShift1024>(&zero).is_err());
+    let out_of_range = format!(r#"{{"s":{:?},"p":16}}"#, [1u64; 16]);
+    assert!(serde_json::from_str::<XorShift1024>(&out_of_range).is_err());
+    let valid = format!(r#"{{"s":{:?},"p":15}}"#, [1u64; 16]);
+    assert!(serde_json::from_str::<XorShift1024>(&valid).is_ok());
+}
+
+#[test]
+fn xorshift1024_seed_serde() {
+    let mut seed = XorShift1024Seed::default();
+    for (i, b) in seed.0.iter_mut().enumerate() {
+        *b = i as u8;
+    }
+
+    let json = serde_json::to_string(&seed).unwrap();
+    let seed1: XorShift1024Seed = serde_json::from_str(&json).unwrap();
+    let bin = bincode::serialize(&seed).unwrap();
+    let seed2: XorShift1024Seed = bincode::deserialize(&bin).unwrap();
+    assert_eq!(&seed1.0[..], &seed.0[..]);
+    assert_eq!(&seed2.0[..], &seed.0[..]);
+
+    let mut rng1 = XorShift1024::from_seed(seed);
+    let mut rng2 = XorShift1024::from_seed(seed1);
+    for _ in 0..20 {
+        assert_eq!(rng1.next_u64(), rng2.next_u64());
+    }
+
+    assert!(serde_json::from_str::<XorShift1024Seed>("[1, 2, 3]").is_err());
+}
+
+#[test]
+fn xoshiro512_seed_serde() {
+    let mut seed = Xoshiro512Seed::default();
+    for (i, b) in seed.0.iter_mut().enumerate() {
+        *b = i as u8;
+    }
+
+    let bin = bincode::serialize(&seed).unwrap();
+    let seed1: Xoshiro512Seed = bincode::deserialize(&bin).unwrap();
+    assert_eq!(&seed1.0[..], &seed.0[..]);
+
+    let json = serde_json::to_string(&Xoshiro512StarStar::from_seed(seed)).unwrap();
+    let rng: Xoshiro512StarStar = serde_json::from_str(&json).unwrap();
+    assert_eq!(rng.state()[0], 0x0706050403020100);
+
+    let json = serde_json::to_string(&seed1).unwrap();
+    assert!(serde_json::from_str::<XorShift1024Seed>(&json).is_err());
+}
@@ -0,0 +1,62 @@
+#![allow(unknown_lints)]
+#![allow(clippy::unreadable_literal)]
+
+extern crate rand;
+extern crate xoroshiro;
+
+use rand::RngCore;
+use xoroshiro::rng::{SplitMix64, StateError, XoroShiro128, XorShift1024};
+
+#[test]
+fn splitmix64_state_roundtrip() {
+    let mut rng1 = SplitMix64::from_seed_u64(1477776061723855037);
+    rng1.next_u64();
+    assert_eq!(rng1.state(), 1477776061723855037u64.wrapping_add(0x9e3779b97f4a7c15));
+    let mut rng2 = SplitMix64::from_state(rng1.state());
+    for _ in 0..10 {
+        assert_eq!(rng1.next_u64(), rng2.next_u64());
+    }
+}
+
+#[test]
+fn xoroshiro128_state_roundtrip() {
+    let rng = XoroShiro128::new_unseeded();
+    assert_eq!(rng.state(), [0x193a6754a8a7d469, 0x97830e05113ba7bb]);
+
+    let mut rng1 = XoroShiro128::from_seed_u64(1477776328140003287);
+    rng1.next_u64();
+    let mut rng2 = XoroShiro128::from_state(rng1.state()).unwrap();
+    assert_eq!(rng1.state(), rng2.state());
+    for _ in 0..10 {
+        assert_eq!(rng1.next_u64(), rng2.next_u64());
+    }
+}
+
+#[test]
+fn xoroshiro128_invalid_state() {
+    assert_eq!(XoroShiro128::from_state([0, 0]).unwrap_err(), StateError::AllZero);
+    assert!(XoroShiro128::from_state([0, 1]).is_ok());
+}
+
+#[test]
+fn xorshift1024_state_roundtrip() {
+    let mut rng1 = XorShift1024::from_seed_u64(1477777179826044140);
+    for _ in 0..21 {
+        rng1.next_u64();
+    }
+    let (_, p) = rng1.state();
+    assert_eq!(p, 5);
+    let mut rng2 = XorShift1024::from_state(rng1.state()).unwrap();
+    for _ in 0..40 {
+        assert_eq!(rng1.next_u64(), rng2.next_u64());
+    }
+}
+
+#[test]
+fn xorshift1024_invalid_state() {
+    assert_eq!(XorShift1024::from_state(([0; 16], 0)).unwrap_err(),
+               StateError::AllZero);
+    assert_eq!(XorShift1024::from_state(([1; 16], 16)).unwrap_err(),
+               StateError::IndexOutOfRange(16));
+    assert!(XorShift1024::from_state(([1; 16], 15)).is_ok());
+}
@@ -0,0 +1,117 @@
+#![allow(unknown_lints)]
+#![allow(clippy::unreadable_literal)]
+
+extern crate rand;
+extern crate xoroshiro;
+
+use rand::RngCore;
+use xoroshiro::rng::{
+    ParseStateError, StateError,
+    SplitMix64, XoroShiro128, XorShift1024, XorShift1024Seed, Xoshiro512Seed,
+};
+
+#[test]
+fn splitmix64_state_str() {
+    let rng = SplitMix64::from_state(0x0123456789abcdef);
+    assert_eq!(rng.to_string(), "splitmix64:0123456789abcdef");
+    let parsed: SplitMix64 = "splitmix64:0123456789abcdef".parse().unwrap();
+    assert_eq!(parsed.state(), 0x0123456789abcdef);
+
+    let rng = SplitMix64::from_state_gamma(0x0123456789abcdef, 0xfedcba9876543211).unwrap();
+    let s = "splitmix64:0123456789abcdef:fedcba9876543211";
+    assert_eq!(rng.to_string(), s);
+    let parsed: SplitMix64 = s.parse().unwrap();
+    assert_eq!(parsed.gamma(), 0xfedcba9876543211);
+    assert_eq!("splitmix64:0123456789abcdef:fedcba9876543210".parse::<SplitMix64>().unwrap_err(),
+               ParseStateError::InvalidState(StateError::EvenGamma));
+}
+
+#[test]
+fn xoroshiro128_state_str() {
+    let s = "xoroshiro128+:193a6754a8a7d469:97830e05113ba7bb";
+    assert_eq!(XoroShiro128::new_unseeded().to_string(), s);
+    let parsed: XoroShiro128 = s.parse().unwrap();
+    assert_eq!(parsed.state(), XoroShiro128::new_unseeded().state());
+
+    let mut rng = XoroShiro128::from_seed_u64(1477776328140003287);
+    rng.next_u64();
+    let mut parsed: XoroShiro128 = rng.to_string().parse().unwrap();
+    assert_eq!(parsed.next_u64(), rng.next_u64());
+}
+
+#[test]
+fn xoroshiro128_state_str_invalid() {
+    fn parse(s: &str) -> Result<XoroShiro128, ParseStateError> {
+        s.parse()
+    }
+    assert_eq!(parse("xoroshiro128:193a6754a8a7d469:97830e05113ba7bb").unwrap_err(),
+               ParseStateError::InvalidAlgorithm);
+    assert_eq!(parse("xoroshiro128+:193a6754a8a7d469").unwrap_err(),
+               ParseStateError::InvalidFieldCount);
+    assert_eq!(parse("xoroshiro128+:193a6754a8a7d469:97830e05113ba7bb:").unwrap_err(),
+               ParseStateError::InvalidFieldCount);
+    assert_eq!(parse("xoroshiro128+:193a6754a8a7d469:97830e05113ba7b").unwrap_err(),
+               ParseStateError::InvalidField(1));
+    assert_eq!(parse("xoroshiro128+:193A6754A8A7D469:97830e05113ba7bb").unwrap_err(),
+               ParseStateError::InvalidField(0));
+    assert_eq!(parse("xoroshiro128+:+93a6754a8a7d469:97830e05113ba7bb").unwrap_err(),
+               ParseStateError::InvalidField(0));
+    assert_eq!(parse("xoroshiro128+:0000000000000000:0000000000000000").unwrap_err(),
+               ParseStateError::InvalidState(StateError::AllZero));
+}
+
+#[test]
+fn xorshift1024_state_str() {
+    let mut rng = XorShift1024::from_seed_u64(1477777179826044140);
+    for _ in 0..21 {
+        rng.next_u64();
+    }
+    let s = rng.to_string();
+    assert!(s.starts_with("xorshift1024*phi:"));
+    assert!(s.ends_with(":5"));
+    assert_eq!(s.len(), "xorshift1024*phi".len() + 16 * 17 + 2);
+    let parsed: XorShift1024 = s.parse().unwrap();
+    assert_eq!(parsed.state(), rng.state());
+}
+
+#[test]
+fn xorshift1024_state_str_invalid() {
+    let words = vec!["0000000000000001"; 16].join(":");
+    let parse = |p: &str| format!("xorshift1024*phi:{}:{}", words, p).parse::<XorShift1024>();
+    assert!(parse("15").is_ok());
+    assert_eq!(parse("16").unwrap_err(),
+               ParseStateError::InvalidState(StateError::IndexOutOfRange(16)));
+    assert_eq!(parse("05").unwrap_err(), ParseStateError::InvalidField(16));
+    assert_eq!(parse("+5").unwrap_err(), ParseStateError::InvalidField(16));
+    assert_eq!(parse("").unwrap_err(), ParseStateError::InvalidField(16));
+}
+
+#[test]
+fn xorshift1024_seed_str() {
+    let mut seed = XorShift1024Seed::default();
+    for (i, b) in seed.0.iter_mut().enumerate() {
+        *b = i as u8;
+    }
+    let s = seed.to_string();
+    assert!(s.starts_with("xorshift1024seed:000102030405"));
+    assert_eq!(s.len(), "xorshift1024seed:".len() + 256);
+    let parsed: XorShift1024Seed = s.parse().unwrap();
+    assert_eq!(&parsed.0[..], &seed.0[..]);
+
+    assert_eq!(s[..s.len() - 1].parse::<XorShift1024Seed>().err(),
+               Some(ParseStateError::InvalidField(0)));
+}
+
+#[test]
+fn xoshiro512_seed_str() {
+    let mut seed = Xoshiro512Seed::default();
+    seed.0[63] = 0xff;
+    let s = seed.to_string();
+    assert_eq!(s.len(), "xoshiro512seed:".len() + 128);
+    assert!(s.ends_with("00ff"));
+    let parsed: Xoshiro512Seed = s.parse().unwrap();
+    assert_eq!(&parsed.0[..], &seed.0[..]);
+
+    assert_eq!("xorshift1024seed:00".parse::<Xoshiro512Seed>().err(),
+               Some(ParseStateError::InvalidAlgorithm));
+}